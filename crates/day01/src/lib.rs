@@ -0,0 +1,83 @@
+use aoc2025::day::{Answer, Day};
+use aoc2025::error::{Error, Result};
+use aoc2025::utils;
+
+#[derive(Debug, Clone, Copy)]
+struct Solution {
+    dial: i32,
+    part1: i32,
+    part2: i32,
+}
+
+/// Simulate a single line of input and update the solution state.
+/// 'line' is expected to match the format L|R\d+.
+///
+/// ## Arguments
+/// * `state` - Current solution state.
+/// * `line_no` - The 1-based number of `line` in the input, used for errors.
+/// * `line` - A string slice representing the current line from input.
+///
+/// ## Returns
+///
+/// * `Solution` - Updated solution state after processing the input line.
+/// * `Error::Parse` - If the line is not a rotation like `L68`.
+///
+fn simulate_line(state: Solution, line_no: usize, line: &str) -> Result<Solution> {
+    const EXPECTED: &str = "a rotation like `L68` or `R14`";
+    let direction = match line.chars().next() {
+        Some('L') => -1,
+        Some('R') => 1,
+        _ => return Err(Error::parse(line_no, EXPECTED, line)),
+    };
+    let mut distance: i32 = utils::parse_value(&line[1..], line_no, EXPECTED)?;
+    let mut zeros = distance / 100;
+    distance %= 100;
+
+    let mut new_dial = state.dial + distance * direction;
+    if new_dial < 0 {
+        new_dial = 100 - new_dial.abs();
+        // crossing zero only counts if we were previously above zero
+        if state.dial > 0 {
+            zeros += 1;
+        }
+    } else if new_dial >= 100 {
+        new_dial %= 100;
+        zeros += 1;
+    } else if new_dial == 0 {
+        zeros += 1;
+    }
+
+    Ok(Solution {
+        dial: new_dial,
+        part1: state.part1 + (state.dial == 0) as i32,
+        part2: state.part2 + zeros,
+    })
+}
+
+fn simulate(input: &str) -> Result<Solution> {
+    let solution = Solution {
+        dial: 50,
+        part1: 0,
+        part2: 0,
+    };
+    input
+        .lines()
+        .enumerate()
+        .try_fold(solution, |state, (idx, line)| simulate_line(state, idx + 1, line))
+}
+
+pub struct Day01;
+
+impl Day for Day01 {
+    fn day(&self) -> u8 {
+        1
+    }
+
+    fn part1(&self, input: &str) -> Result<Answer> {
+        Ok(simulate(input)?.part1.into())
+    }
+
+    fn part2(&self, input: &str) -> Result<Answer> {
+        Ok(simulate(input)?.part2.into())
+    }
+}
@@ -0,0 +1,58 @@
+use aoc2025::day::{Answer, Day};
+use aoc2025::digits::{self, Repetitions};
+use aoc2025::error::{Error, Result};
+use aoc2025::utils;
+use regex::Regex;
+
+#[derive(Debug, Clone, Copy)]
+struct Solution {
+    part1: u128,
+    part2: u128,
+}
+
+fn parse_ranges(input: &str) -> Result<Vec<(u64, u64)>> {
+    const EXPECTED: &str = "comma separated ranges like `11-22`";
+    let re = Regex::new(r"^(?P<start>\d+)-(?P<end>\d+)$").unwrap();
+    let mut ranges = Vec::new();
+    for (idx, line) in input.lines().enumerate() {
+        for range in line.split(',').map(str::trim).filter(|r| !r.is_empty()) {
+            let captures = re
+                .captures(range)
+                .ok_or_else(|| Error::parse(idx + 1, EXPECTED, range))?;
+            let start = utils::parse_value(&captures["start"], idx + 1, EXPECTED)?;
+            let end = utils::parse_value(&captures["end"], idx + 1, EXPECTED)?;
+            ranges.push((start, end));
+        }
+    }
+    Ok(ranges)
+}
+
+fn solve(input: &str) -> Result<Solution> {
+    let ranges = parse_ranges(input)?;
+    let sum = |repetitions| {
+        ranges
+            .iter()
+            .map(|&(start, end)| digits::repeated_blocks(start, end, 10, repetitions).sum)
+            .sum()
+    };
+    Ok(Solution {
+        part1: sum(Repetitions::Exactly(2)),
+        part2: sum(Repetitions::AtLeast(2)),
+    })
+}
+
+pub struct Day02;
+
+impl Day for Day02 {
+    fn day(&self) -> u8 {
+        2
+    }
+
+    fn part1(&self, input: &str) -> Result<Answer> {
+        Ok(solve(input)?.part1.into())
+    }
+
+    fn part2(&self, input: &str) -> Result<Answer> {
+        Ok(solve(input)?.part2.into())
+    }
+}
@@ -0,0 +1,58 @@
+use aoc2025::day::{Answer, Day};
+use aoc2025::digits::{self, Pick};
+use aoc2025::error::{Error, Result};
+
+#[derive(Debug, Clone, Copy)]
+struct Solution {
+    part1: u128,
+    part2: u128,
+}
+
+fn parse_bank(line_no: usize, line: &str) -> Result<Vec<u8>> {
+    const EXPECTED: &str = "a bank of at least 12 battery digits";
+    let bank = line
+        .chars()
+        .map(|c| c.to_digit(10).map(|d| d as u8))
+        .collect::<Option<Vec<u8>>>()
+        .ok_or_else(|| Error::parse(line_no, EXPECTED, line))?;
+    if bank.len() < 12 {
+        return Err(Error::parse(line_no, EXPECTED, line));
+    }
+    Ok(bank)
+}
+
+/// The largest joltage from turning on `count` batteries of `bank`.
+fn joltage(bank: &[u8], count: usize) -> u128 {
+    let batteries = digits::pick_digits(bank, count, Pick::Largest)
+        .expect("banks are checked to have enough batteries");
+    digits::from_digits(&batteries, 10).expect("the joltage fits in a u128")
+}
+
+fn solve(input: &str) -> Result<Solution> {
+    let banks = input
+        .lines()
+        .enumerate()
+        .map(|(idx, line)| parse_bank(idx + 1, line))
+        .collect::<Result<Vec<_>>>()?;
+
+    Ok(Solution {
+        part1: banks.iter().map(|bank| joltage(bank, 2)).sum(),
+        part2: banks.iter().map(|bank| joltage(bank, 12)).sum(),
+    })
+}
+
+pub struct Day03;
+
+impl Day for Day03 {
+    fn day(&self) -> u8 {
+        3
+    }
+
+    fn part1(&self, input: &str) -> Result<Answer> {
+        Ok(solve(input)?.part1.into())
+    }
+
+    fn part2(&self, input: &str) -> Result<Answer> {
+        Ok(solve(input)?.part2.into())
+    }
+}
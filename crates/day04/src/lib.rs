@@ -0,0 +1,44 @@
+use aoc2025::day::{Answer, Day};
+use aoc2025::error::Result;
+use aoc2025::grid::{Grid, GridCell};
+
+#[derive(Debug, Clone, Copy)]
+struct Solution {
+    part1: i64,
+    part2: i64,
+}
+
+fn can_reach(cell: &GridCell, grid: &Grid) -> bool {
+    let neighbors = grid.count_neighbors_with(cell.position, |v| v == '@');
+    cell.value == '@' && neighbors < 4
+}
+
+fn solve(input: &str) -> Result<Solution> {
+    let mut solution = Solution { part1: 0, part2: 0 };
+    let mut grid: Grid = input.parse()?;
+
+    let mut removed = grid.update_cells_where('.', can_reach) as i64;
+    solution.part1 += removed;
+
+    while removed > 0 {
+        solution.part2 += removed;
+        removed = grid.update_cells_where('.', can_reach) as i64;
+    }
+    Ok(solution)
+}
+
+pub struct Day04;
+
+impl Day for Day04 {
+    fn day(&self) -> u8 {
+        4
+    }
+
+    fn part1(&self, input: &str) -> Result<Answer> {
+        Ok(solve(input)?.part1.into())
+    }
+
+    fn part2(&self, input: &str) -> Result<Answer> {
+        Ok(solve(input)?.part2.into())
+    }
+}
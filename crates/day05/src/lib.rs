@@ -0,0 +1,243 @@
+use aoc2025::day::{Alternative, Answer, Breakdown, Day};
+use aoc2025::error::{Error, Result};
+use aoc2025::interval::{Interval, IntervalSet};
+use aoc2025::utils;
+use regex::Regex;
+use std::io;
+use std::path::Path;
+use std::process::{Command, Stdio};
+
+#[derive(Debug, Clone, Copy)]
+struct Solution {
+    part1: usize,
+    part2: u128,
+}
+
+/// The fresh ingredient ranges as given, and each ingredient ID with the
+/// line it's on.
+struct Inventory {
+    ranges: Vec<Interval<usize>>,
+    ingredients: Vec<(usize, usize)>,
+}
+
+fn parse_input(input: &str) -> Result<Inventory> {
+    const RANGE: &str = "a fresh ingredient range like `3-5`";
+    const INGREDIENT: &str = "an ingredient ID";
+    let ranges_regex = Regex::new(r"^(?P<start>\d+)\-(?P<end>\d+)$").unwrap();
+
+    let mut ranges = Vec::new();
+    let mut ingredients = Vec::new();
+    let mut in_ranges = true;
+    for (idx, line) in input.lines().enumerate() {
+        let line_no = idx + 1;
+        if in_ranges && line.trim().is_empty() {
+            in_ranges = false;
+        } else if in_ranges {
+            let cap = ranges_regex
+                .captures(line.trim())
+                .ok_or_else(|| Error::parse(line_no, RANGE, line))?;
+            let range = Interval::closed(
+                utils::parse_value(&cap["start"], line_no, RANGE)?,
+                utils::parse_value(&cap["end"], line_no, RANGE)?,
+            )
+            .ok_or_else(|| {
+                Error::parse(line_no, "a range that doesn't end before it starts", line)
+            })?;
+            ranges.push(range);
+        } else if !line.trim().is_empty() {
+            ingredients.push((line_no, utils::parse_value(line, line_no, INGREDIENT)?));
+        }
+    }
+    if in_ranges {
+        let end = input.lines().count() + 1;
+        return Err(Error::parse(
+            end,
+            "a blank line between ranges and ingredients",
+            "end of input",
+        ));
+    }
+    Ok(Inventory {
+        ranges,
+        ingredients,
+    })
+}
+
+fn solve(input: &str) -> Result<Solution> {
+    let inventory = parse_input(input)?;
+    let ranges: IntervalSet<usize> = inventory.ranges.iter().copied().collect();
+    Ok(Solution {
+        part1: inventory
+            .ingredients
+            .iter()
+            .filter(|&&(_, id)| ranges.contains(id))
+            .count(),
+        part2: ranges.covered_count(),
+    })
+}
+
+/// Part 1, marking each ingredient fresh or spoiled.
+fn freshness(input: &str, fresh: impl Fn(&Inventory, usize) -> bool) -> Result<Breakdown> {
+    let inventory = parse_input(input)?;
+    let items: Vec<(usize, Answer)> = inventory
+        .ingredients
+        .iter()
+        .map(|&(line, id)| {
+            let state = if fresh(&inventory, id) {
+                "fresh"
+            } else {
+                "spoiled"
+            };
+            (line, state.into())
+        })
+        .collect();
+    let total = items
+        .iter()
+        .filter(|(_, state)| state.as_str() == "fresh")
+        .count();
+    Ok(Breakdown::new(total, items))
+}
+
+/// The ranges as `(start, end)` pairs, sorted and with every overlapping or
+/// adjacent pair merged, by hand rather than with an [`IntervalSet`].
+fn sorted_and_merged(ranges: &[Interval<usize>]) -> Vec<(usize, usize)> {
+    let mut sorted: Vec<(usize, usize)> = ranges
+        .iter()
+        .map(|range| (range.start, range.end))
+        .collect();
+    sorted.sort_unstable();
+    let mut merged: Vec<(usize, usize)> = Vec::new();
+    for (start, end) in sorted {
+        match merged.last_mut() {
+            Some((_, last)) if start <= last.saturating_add(1) => *last = end.max(*last),
+            _ => merged.push((start, end)),
+        }
+    }
+    merged
+}
+
+/// Part 1 with a binary search of the sorted and merged ranges.
+fn fresh_by_sort_and_merge(input: &str) -> Result<Breakdown> {
+    let merged = sorted_and_merged(&parse_input(input)?.ranges);
+    freshness(input, |_, id| {
+        let after = merged.partition_point(|&(start, _)| start <= id);
+        after > 0 && id <= merged[after - 1].1
+    })
+}
+
+/// Part 1 checking every range for every ingredient.
+fn fresh_by_linear_scan(input: &str) -> Result<Breakdown> {
+    freshness(input, |inventory, id| {
+        inventory.ranges.iter().any(|range| range.contains(id))
+    })
+}
+
+/// Part 2 adding up the sorted and merged ranges.
+fn covered_by_sort_and_merge(input: &str) -> Result<Breakdown> {
+    let merged = sorted_and_merged(&parse_input(input)?.ranges);
+    let total: u128 = merged
+        .iter()
+        .map(|&(start, end)| (end - start) as u128 + 1)
+        .sum();
+    Ok(Breakdown::new(total, Vec::new()))
+}
+
+/// Path of the original `day05.sh`, at the top of the workspace.
+const SCRIPT: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/../../day05.sh");
+
+/// Whether [`covered_by_script`] can run: the script has to be there, which
+/// it only is in a checkout of the workspace, and so does `bash`.
+fn script_available() -> std::result::Result<(), String> {
+    if !Path::new(SCRIPT).is_file() {
+        return Err(format!("{} not found", SCRIPT));
+    }
+    let bash = Command::new("bash")
+        .arg("--version")
+        .stdout(Stdio::null())
+        .status();
+    match bash {
+        Ok(status) if status.success() => Ok(()),
+        _ => Err("bash not found".to_string()),
+    }
+}
+
+/// Part 2 with the original `day05.sh`, which needs `bash`.
+fn covered_by_script(input: &str) -> Result<Breakdown> {
+    parse_input(input)?;
+    let io_error = |source| Error::Io {
+        path: SCRIPT.to_string(),
+        source,
+    };
+
+    // The script reads its input from a file named on the command line.
+    let path = std::env::temp_dir().join(format!("day05-check-{}", std::process::id()));
+    std::fs::write(&path, input).map_err(|source| Error::Io {
+        path: path.display().to_string(),
+        source,
+    })?;
+    let output = Command::new("bash")
+        .arg(SCRIPT)
+        .arg(&path)
+        .stderr(Stdio::inherit())
+        .output();
+    let _ = std::fs::remove_file(&path);
+    let output = output.map_err(io_error)?;
+    if !output.status.success() {
+        return Err(io_error(io::Error::other(format!(
+            "exited with {}",
+            output.status
+        ))));
+    }
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let total: u128 = stdout.trim().parse().map_err(|_| {
+        io_error(io::Error::other(format!(
+            "printed {:?} instead of a count",
+            stdout.trim()
+        )))
+    })?;
+    Ok(Breakdown::new(total, Vec::new()))
+}
+
+pub struct Day05;
+
+impl Day for Day05 {
+    fn day(&self) -> u8 {
+        5
+    }
+
+    fn part1(&self, input: &str) -> Result<Answer> {
+        Ok(solve(input)?.part1.into())
+    }
+
+    fn part2(&self, input: &str) -> Result<Answer> {
+        Ok(solve(input)?.part2.into())
+    }
+
+    fn alternatives(&self) -> Vec<Alternative> {
+        vec![
+            Alternative {
+                part: 1,
+                name: "sort-and-merge",
+                solve: fresh_by_sort_and_merge,
+                requires: None,
+            },
+            Alternative {
+                part: 1,
+                name: "linear-scan",
+                solve: fresh_by_linear_scan,
+                requires: None,
+            },
+            Alternative {
+                part: 2,
+                name: "sort-and-merge",
+                solve: covered_by_sort_and_merge,
+                requires: None,
+            },
+            Alternative {
+                part: 2,
+                name: "day05.sh",
+                solve: covered_by_script,
+                requires: Some(script_available),
+            },
+        ]
+    }
+}
@@ -0,0 +1,126 @@
+use aoc2025::day::{Answer, Day};
+use aoc2025::error::{Error, Result};
+use aoc2025::utils;
+use regex::Regex;
+use std::cmp::{max, min};
+
+#[derive(Debug, Clone, Copy)]
+struct Solution {
+    part1: usize,
+    part2: usize,
+}
+
+fn solve(input: &str) -> Result<Solution> {
+    let col_separator = Regex::new(r"\s+").unwrap();
+    let mut solution = Solution { part1: 0, part2: 0 };
+    let mut grid: Vec<Vec<String>> = Vec::new();
+
+    for line in input.lines() {
+        let mut columns: Vec<String> = Vec::new();
+        for col in col_separator.split(line) {
+            if col.is_empty() {
+                continue;
+            }
+            columns.push(col.to_string());
+        }
+        grid.push(columns);
+    }
+
+    let operators_line = input.lines().count();
+    let operators = grid
+        .pop()
+        .ok_or_else(|| Error::parse(1, "rows of numbers followed by operators", ""))?;
+    if let Some(operator) = operators.iter().find(|&o| o != "*" && o != "+") {
+        return Err(Error::parse(operators_line, "`*` or `+`", operator));
+    }
+    for (idx, row) in grid.iter().enumerate() {
+        if row.len() != operators.len() {
+            let expected = format!("{} numbers", operators.len());
+            return Err(Error::parse(idx + 1, expected, row.join(" ")));
+        }
+    }
+
+    let mut columns: Vec<Vec<String>> = Vec::new();
+    for (i, operator) in operators.iter().enumerate() {
+        let mut column: Vec<String> = Vec::new();
+        let mut acc = 0;
+        for (idx, item) in grid.iter().enumerate() {
+            column.push(item[i].clone());
+            let num: usize = utils::parse_value(&item[i], idx + 1, "a number")?;
+            acc = if acc == 0 {
+                num
+            } else if operator == "*" {
+                acc * num
+            } else {
+                acc + num
+            };
+        }
+        solution.part1 += acc;
+        columns.push(column);
+    }
+
+    let mut column_lengths: Vec<usize> = Vec::new();
+    for column in columns {
+        column_lengths.push(column.iter().fold(0, |acc, c| max(acc, c.len())));
+    }
+
+    let mut grid: Vec<Vec<String>> = Vec::new();
+    for line in input.lines() {
+        let mut current = 0;
+        let mut row: Vec<String> = Vec::new();
+        for length in &column_lengths {
+            let col = line
+                .get(current..min(line.len(), current + length))
+                .unwrap_or("")
+                .to_string();
+            row.push(col);
+            current += length + 1;
+        }
+        grid.push(row);
+    }
+    _ = grid.pop();
+
+    for (i, operator) in operators.iter().enumerate() {
+        let operator = if operator == "*" {
+            |n1: usize, n2: usize| n1 * n2
+        } else {
+            |n1: usize, n2: usize| n1 + n2
+        };
+        let mut column: Vec<String> = Vec::new();
+        for row in grid.iter() {
+            column.push(row[i].clone());
+        }
+
+        let mut numbers: Vec<usize> = Vec::new();
+        for l in (0..column_lengths[i]).rev() {
+            let mut number: String = String::new();
+            for n in column.iter() {
+                if let Some(c) = n.chars().nth(l)
+                    && c != ' '
+                {
+                    number.push(c);
+                }
+            }
+            let expected = "a digit in every character column of a problem";
+            numbers.push(utils::parse_value(&number, operators_line, expected)?);
+        }
+        solution.part2 += numbers.iter().cloned().reduce(operator).unwrap_or(0);
+    }
+    Ok(solution)
+}
+
+pub struct Day06;
+
+impl Day for Day06 {
+    fn day(&self) -> u8 {
+        6
+    }
+
+    fn part1(&self, input: &str) -> Result<Answer> {
+        Ok(solve(input)?.part1.into())
+    }
+
+    fn part2(&self, input: &str) -> Result<Answer> {
+        Ok(solve(input)?.part2.into())
+    }
+}
@@ -0,0 +1,111 @@
+use aoc2025::day::{Alternative, Answer, Breakdown, Day};
+use aoc2025::disjoint_set::DisjointSet;
+use aoc2025::error::{Error, Result};
+use aoc2025::point::Point3;
+use aoc2025::spatial::{self, KdTree, Pair};
+
+fn parse_boxes(input: &str) -> Result<Vec<Point3<i64>>> {
+    const EXPECTED: &str = "a junction box position like `162,817,812`";
+    input
+        .lines()
+        .enumerate()
+        .map(|(idx, line)| Point3::parse(line, idx + 1, EXPECTED))
+        .collect()
+}
+
+/// The product of the three largest circuits once the first 1000 of `pairs`
+/// are connected.
+fn largest_circuits(boxes: &[Point3<i64>], pairs: impl Iterator<Item = Pair>) -> usize {
+    let mut circuits = DisjointSet::new(boxes.len());
+    for pair in pairs.take(1000) {
+        circuits.union(pair.a, pair.b);
+    }
+    circuits.largest_components(3).iter().product()
+}
+
+/// The product of the X coordinates of the last two boxes to join.
+fn last_connection(boxes: &[Point3<i64>], last: Option<Pair>, input: &str) -> Result<i64> {
+    let last = last.ok_or_else(|| {
+        let end = input.lines().count() + 1;
+        Error::parse(end, "at least two junction boxes", "end of input")
+    })?;
+    Ok(boxes[last.a].x * boxes[last.b].x)
+}
+
+/// Every pair of boxes, nearest first, by sorting the full list.
+fn sorted_pairs(boxes: &[Point3<i64>]) -> Vec<Pair> {
+    let mut pairs = Vec::new();
+    for a in 0..boxes.len() {
+        for b in a + 1..boxes.len() {
+            let squared_distance = boxes[a].squared_distance(boxes[b]);
+            pairs.push(Pair {
+                squared_distance,
+                a,
+                b,
+            });
+        }
+    }
+    pairs.sort_unstable();
+    pairs
+}
+
+fn largest_circuits_by_sorting(input: &str) -> Result<Breakdown> {
+    let boxes = parse_boxes(input)?;
+    let product = largest_circuits(&boxes, sorted_pairs(&boxes).into_iter());
+    Ok(Breakdown::new(product, Vec::new()))
+}
+
+/// Part 2 with Kruskal's algorithm over the sorted pairs.
+fn last_connection_by_sorting(input: &str) -> Result<Breakdown> {
+    let boxes = parse_boxes(input)?;
+    let mut circuits = DisjointSet::new(boxes.len());
+    let mut last = None;
+    for pair in sorted_pairs(&boxes) {
+        if circuits.components() == 1 {
+            break;
+        }
+        if !circuits.same(pair.a, pair.b) {
+            circuits.union(pair.a, pair.b);
+            last = Some(pair);
+        }
+    }
+    let product = last_connection(&boxes, last, input)?;
+    Ok(Breakdown::new(product, Vec::new()))
+}
+
+pub struct Day08;
+
+impl Day for Day08 {
+    fn day(&self) -> u8 {
+        8
+    }
+
+    fn part1(&self, input: &str) -> Result<Answer> {
+        let boxes = parse_boxes(input)?;
+        let tree = KdTree::new(&boxes);
+        Ok(largest_circuits(&boxes, tree.nearest_pairs()).into())
+    }
+
+    fn part2(&self, input: &str) -> Result<Answer> {
+        let boxes = parse_boxes(input)?;
+        let last = spatial::minimum_spanning_tree(&boxes).pop();
+        Ok(last_connection(&boxes, last, input)?.into())
+    }
+
+    fn alternatives(&self) -> Vec<Alternative> {
+        vec![
+            Alternative {
+                part: 1,
+                name: "sorted-pairs",
+                solve: largest_circuits_by_sorting,
+                requires: None,
+            },
+            Alternative {
+                part: 2,
+                name: "sorted-pairs",
+                solve: last_connection_by_sorting,
+                requires: None,
+            },
+        ]
+    }
+}
@@ -0,0 +1,81 @@
+use aoc2025::compress::CompressedPlane;
+use aoc2025::day::{Answer, Day};
+use aoc2025::error::{Error, Result};
+use aoc2025::point::Point2;
+use aoc2025::polygon::RectilinearPolygon;
+use itertools::Itertools;
+
+#[derive(Debug, Clone, Copy)]
+struct Solution {
+    part1: u128,
+    part2: u128,
+}
+
+fn parse_points(input: &str) -> Result<Vec<Point2<i64>>> {
+    const EXPECTED: &str = "a red tile position like `7,1`";
+    input
+        .lines()
+        .enumerate()
+        .map(|(idx, l)| Point2::parse(l, idx + 1, EXPECTED))
+        .collect()
+}
+
+/// Number of tiles in the rectangle with opposite corners `a` and `b`.
+fn tiles(a: Point2<i64>, b: Point2<i64>) -> u128 {
+    (a.x.abs_diff(b.x) as u128 + 1) * (a.y.abs_diff(b.y) as u128 + 1)
+}
+
+fn solve(input: &str) -> Result<Solution> {
+    let points = parse_points(input)?;
+    if points.len() < 2 {
+        return Err(Error::parse(1, "at least two red tiles", input.trim()));
+    }
+    let part1 = points
+        .iter()
+        .tuple_combinations()
+        .map(|(&a, &b)| tiles(a, b))
+        .max()
+        .unwrap_or(0);
+
+    let lines: Vec<&str> = input.lines().collect();
+    let plane = CompressedPlane::new(&points).map_err(|e| {
+        let idx = points
+            .iter()
+            .position(|p| p.x == e.value || p.y == e.value)
+            .unwrap_or(0);
+        Error::parse(
+            idx + 1,
+            "a red tile with room for a border around it",
+            lines[idx],
+        )
+    })?;
+    let floor = RectilinearPolygon::new(points).map_err(|e| {
+        let next = (e.vertex + 1) % lines.len();
+        Error::parse(
+            next + 1,
+            "a red tile in the same row or column as the one before",
+            lines[next],
+        )
+    })?;
+    let part2 = plane
+        .largest_cornered_rectangle(&floor.draw(&plane), |tile| tile != '.', floor.vertices())
+        .map_or(0, |rectangle| rectangle.area);
+
+    Ok(Solution { part1, part2 })
+}
+
+pub struct Day09;
+
+impl Day for Day09 {
+    fn day(&self) -> u8 {
+        9
+    }
+
+    fn part1(&self, input: &str) -> Result<Answer> {
+        Ok(solve(input)?.part1.into())
+    }
+
+    fn part2(&self, input: &str) -> Result<Answer> {
+        Ok(solve(input)?.part2.into())
+    }
+}
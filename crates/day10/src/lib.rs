@@ -0,0 +1,291 @@
+use aoc2025::day::{Alternative, Answer, Breakdown, Day};
+use aoc2025::error::{Error, Result};
+use aoc2025::gf2::{BitVector, LinearSystem, TooManySolutions, Unsolvable};
+use aoc2025::ilp::IntegerSystem;
+use aoc2025::solver::{Expression, Problem, Solution, SolveError};
+use aoc2025::utils;
+use regex::Regex;
+use std::collections::{HashSet, VecDeque};
+
+type Button = Vec<usize>;
+
+/// Why [`Machine::power_on`] found no buttons to press.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum PowerOnError {
+    Unsolvable(Unsolvable),
+    TooManySolutions(TooManySolutions),
+}
+
+impl From<Unsolvable> for PowerOnError {
+    fn from(error: Unsolvable) -> Self {
+        PowerOnError::Unsolvable(error)
+    }
+}
+
+impl From<TooManySolutions> for PowerOnError {
+    fn from(error: TooManySolutions) -> Self {
+        PowerOnError::TooManySolutions(error)
+    }
+}
+
+impl std::fmt::Display for PowerOnError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PowerOnError::Unsolvable(error) => write!(f, "{}", error),
+            PowerOnError::TooManySolutions(error) => write!(f, "{}", error),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Machine {
+    /// The input line the machine is described on.
+    line: usize,
+    indicators: Vec<bool>,
+    target: Vec<bool>,
+    buttons: Vec<Button>,
+    joltages: Vec<usize>,
+}
+
+impl Machine {
+    /// The fewest buttons to press to get from the indicators to the target.
+    /// Pressing a button twice cancels out, so each is pressed at most once
+    /// and the presses form a linear system over GF(2), one equation per
+    /// indicator.
+    pub fn power_on(&self) -> std::result::Result<Vec<usize>, PowerOnError> {
+        let mut system = LinearSystem::new(self.buttons.len());
+        for (idx, (&on, &target)) in self.indicators.iter().zip(&self.target).enumerate() {
+            let toggles = self
+                .buttons
+                .iter()
+                .enumerate()
+                .filter(|(_, button)| button.contains(&idx))
+                .map(|(bidx, _)| bidx);
+            system.equation(
+                BitVector::from_indices(self.buttons.len(), toggles),
+                on != target,
+            );
+        }
+        Ok(system.solve()?.minimum_weight()?.ones().collect())
+    }
+
+    /// The fewest buttons to press to get from the indicators to the target,
+    /// by breadth-first search over the states of the indicators. `None` if
+    /// the target can't be reached.
+    pub fn power_on_by_search(&self) -> Option<usize> {
+        let mut seen = HashSet::from([self.indicators.clone()]);
+        let mut queue = VecDeque::from([(self.indicators.clone(), 0)]);
+        while let Some((state, presses)) = queue.pop_front() {
+            if state == self.target {
+                return Some(presses);
+            }
+            for button in &self.buttons {
+                let mut next = state.clone();
+                for &idx in button {
+                    next[idx] = !next[idx];
+                }
+                if seen.insert(next.clone()) {
+                    queue.push_back((next, presses + 1));
+                }
+            }
+        }
+        None
+    }
+
+    /// The fewest button presses that raise every counter from zero to its
+    /// joltage, with the integer program solved by `solve`.
+    pub fn configure_joltages(
+        &self,
+        solve: impl Fn(&Problem) -> std::result::Result<Solution, SolveError>,
+    ) -> std::result::Result<usize, SolveError> {
+        let mut problem = Problem::new();
+        let presses: Vec<_> = self.buttons.iter().map(|_| problem.integer(0..)).collect();
+        for (idx, &joltage) in self.joltages.iter().enumerate() {
+            let increases: Expression = self
+                .buttons
+                .iter()
+                .zip(&presses)
+                .filter(|(button, _)| button.contains(&idx))
+                .map(|(_, &press)| press)
+                .sum();
+            problem.equal(increases, joltage as i64);
+        }
+        let total: Expression = presses.iter().copied().sum();
+        problem.minimise(total.clone());
+        let solution = solve(&problem)?;
+        Ok(solution.evaluate(&total) as usize)
+    }
+
+    /// [`Machine::configure_joltages`] as an [`IntegerSystem`], one equation
+    /// per counter.
+    pub fn configure_joltages_by_system(&self) -> Option<usize> {
+        let mut system = IntegerSystem::new(self.buttons.len());
+        for (idx, &joltage) in self.joltages.iter().enumerate() {
+            let increases = self
+                .buttons
+                .iter()
+                .map(|button| u64::from(button.contains(&idx)))
+                .collect();
+            system.equation(increases, joltage as u64);
+        }
+        let presses = system.minimise_sum().ok()?;
+        Some(presses.iter().sum::<u64>() as usize)
+    }
+}
+
+/// Solve every machine with `presses`, breaking the total down by machine.
+fn presses_per_machine<E: std::fmt::Display>(
+    input: &str,
+    presses: impl Fn(&Machine) -> std::result::Result<usize, E>,
+) -> Result<Breakdown> {
+    let lines: Vec<&str> = input.lines().collect();
+    let mut total = 0;
+    let mut items = Vec::new();
+    for machine in parse_machines(input)? {
+        let count = presses(&machine).map_err(|e| {
+            let expected = format!("a machine that can be solved, but {e}");
+            Error::parse(machine.line, expected, lines[machine.line - 1])
+        })?;
+        total += count;
+        items.push((machine.line, count.into()));
+    }
+    Ok(Breakdown::new(total, items))
+}
+
+fn power_on_by_search(input: &str) -> Result<Breakdown> {
+    presses_per_machine(input, |machine| {
+        machine.power_on_by_search().ok_or(Unsolvable)
+    })
+}
+
+/// Part 2 with the built-in solver, which is only another way of solving
+/// it when the usual one is Z3.
+#[cfg(feature = "z3")]
+fn configure_by_built_in(input: &str) -> Result<Breakdown> {
+    use aoc2025::solver::{Backend, BuiltIn};
+    presses_per_machine(input, |machine| {
+        machine.configure_joltages(|problem| BuiltIn.solve(problem))
+    })
+}
+
+fn configure_by_system(input: &str) -> Result<Breakdown> {
+    presses_per_machine(input, |machine| {
+        machine
+            .configure_joltages_by_system()
+            .ok_or(SolveError::Infeasible)
+    })
+}
+
+fn parse_machines(input: &str) -> Result<Vec<Machine>> {
+    const EXPECTED: &str = "a machine like `[.##.] (3) (1,3) {3,5,4,7}`";
+    let input_re = Regex::new(
+        r"^(?P<indicators>\[[\.#]+\]) (?P<buttons>(\((\d+,?)+\) )+)(?P<joltages>\{(\d+,?)+\})$",
+    )
+    .unwrap();
+
+    let mut machines = Vec::new();
+    for (idx, line) in input.lines().enumerate() {
+        let line_no = idx + 1;
+        let cap = input_re
+            .captures(line.trim())
+            .ok_or_else(|| Error::parse(line_no, EXPECTED, line))?;
+        let target = cap["indicators"]
+            .trim_matches(['[', ']'])
+            .chars()
+            .map(|c| c == '#')
+            .collect::<Vec<bool>>();
+        let indicators = vec![false; target.len()];
+        let buttons = cap["buttons"]
+            .trim()
+            .split(' ')
+            .map(|button| {
+                button
+                    .trim_matches(['(', ')'])
+                    .split(',')
+                    .map(|i| utils::parse_value(i, line_no, EXPECTED))
+                    .collect::<Result<Vec<usize>>>()
+            })
+            .collect::<Result<Vec<Button>>>()?;
+        if let Some(button) = buttons.iter().find(|b| b.iter().any(|&i| i >= target.len())) {
+            let expected = format!("buttons wired to indicators below {}", target.len());
+            return Err(Error::parse(line_no, expected, format!("{button:?}")));
+        }
+        let joltages = cap["joltages"]
+            .trim_matches(['{', '}'])
+            .split(',')
+            .map(|i| utils::parse_value(i, line_no, EXPECTED))
+            .collect::<Result<Vec<usize>>>()?;
+
+        machines.push(Machine {
+            line: line_no,
+            indicators,
+            target,
+            buttons,
+            joltages,
+        });
+    }
+    Ok(machines)
+}
+
+pub struct Day10;
+
+impl Day for Day10 {
+    fn day(&self) -> u8 {
+        10
+    }
+
+    fn part1(&self, input: &str) -> Result<Answer> {
+        let lines: Vec<&str> = input.lines().collect();
+        let mut presses = 0;
+        for machine in parse_machines(input)? {
+            let buttons = machine.power_on().map_err(|e| {
+                let expected = format!(
+                    "a machine whose buttons can set the indicators to the target, but {e}"
+                );
+                Error::parse(machine.line, expected, lines[machine.line - 1])
+            })?;
+            presses += buttons.len();
+        }
+        Ok(presses.into())
+    }
+
+    fn part2(&self, input: &str) -> Result<Answer> {
+        let lines: Vec<&str> = input.lines().collect();
+        let mut presses = 0;
+        for machine in parse_machines(input)? {
+            presses += machine.configure_joltages(Problem::solve).map_err(|e| {
+                let expected = format!(
+                    "a machine whose buttons can raise the counters to the joltages, but {e}"
+                );
+                Error::parse(machine.line, expected, lines[machine.line - 1])
+            })?;
+        }
+        Ok(presses.into())
+    }
+
+    fn alternatives(&self) -> Vec<Alternative> {
+        #[cfg_attr(not(feature = "z3"), allow(unused_mut))]
+        let mut alternatives = vec![
+            Alternative {
+                part: 1,
+                name: "bfs",
+                solve: power_on_by_search,
+                requires: None,
+            },
+            Alternative {
+                part: 2,
+                name: "integer-system",
+                solve: configure_by_system,
+                requires: None,
+            },
+        ];
+        #[cfg(feature = "z3")]
+        alternatives.push(Alternative {
+            part: 2,
+            name: "built-in",
+            solve: configure_by_built_in,
+            requires: None,
+        });
+        alternatives
+    }
+}
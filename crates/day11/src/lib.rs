@@ -0,0 +1,82 @@
+use aoc2025::day::{Answer, Day};
+use aoc2025::error::{Error, Result};
+use aoc2025::graph::{Cycle, DotOptions, Graph};
+
+/// The number of paths from the device `from` to `to` through every one of
+/// `waypoints`, none if a device isn't in the network.
+fn count_paths(
+    graph: &Graph,
+    from: &str,
+    to: &str,
+    waypoints: &[&str],
+    input: &str,
+) -> Result<u128> {
+    let (Some(from), Some(to)) = (graph.node(from), graph.node(to)) else {
+        return Ok(0);
+    };
+    let Some(waypoints) = waypoints
+        .iter()
+        .map(|&name| graph.node(name))
+        .collect::<Option<Vec<_>>>()
+    else {
+        return Ok(0);
+    };
+    graph
+        .count_paths_via(from, to, &waypoints)
+        .map_err(|cycle| loop_error(graph, &cycle, input))
+}
+
+/// Report a loop of devices at the line listing the first device on it.
+fn loop_error(graph: &Graph, cycle: &Cycle, input: &str) -> Error {
+    let device = graph.name(cycle.nodes[0]);
+    let (line_no, line) = input
+        .lines()
+        .enumerate()
+        .find(|(_, line)| {
+            line.split_once(':')
+                .is_some_and(|(node, _)| node.trim() == device)
+        })
+        .map_or((1, ""), |(idx, line)| (idx + 1, line));
+    Error::parse(
+        line_no,
+        format!("devices without a loop, but {cycle}"),
+        line,
+    )
+}
+
+/// The device network in DOT, with the devices the parts start, end or stop
+/// at highlighted, chains of devices collapsed and each device labelled with
+/// its number of paths to `out`, when that's finite.
+pub fn network_dot(input: &str) -> Result<String> {
+    let graph = Graph::parse(input)?;
+    let highlight_nodes = ["you", "svr", "fft", "dac", "out"]
+        .iter()
+        .filter_map(|&name| graph.node(name))
+        .collect();
+    let path_counts = graph.node("out").and_then(|out| graph.paths_to(out).ok());
+    let options = DotOptions {
+        highlight_nodes,
+        collapse_chains: true,
+        path_counts,
+        ..DotOptions::default()
+    };
+    Ok(graph.to_dot(&options))
+}
+
+pub struct Day11;
+
+impl Day for Day11 {
+    fn day(&self) -> u8 {
+        11
+    }
+
+    fn part1(&self, input: &str) -> Result<Answer> {
+        let graph = Graph::parse(input)?;
+        Ok(count_paths(&graph, "you", "out", &[], input)?.into())
+    }
+
+    fn part2(&self, input: &str) -> Result<Answer> {
+        let graph = Graph::parse(input)?;
+        Ok(count_paths(&graph, "svr", "out", &["fft", "dac"], input)?.into())
+    }
+}
@@ -0,0 +1,137 @@
+use aoc2025::day::{Answer, Day};
+use aoc2025::error::{Error, Result};
+use aoc2025::grid::Grid;
+use aoc2025::polyomino::{self, Packing, Polyomino};
+use aoc2025::utils;
+
+#[derive(Debug)]
+struct Space {
+    grid: Grid,
+    required_presents: Vec<usize>,
+}
+
+/// A way to fit every present the space needs under its tree.
+fn pack(space: &Space, shapes: &[Polyomino]) -> Option<Packing> {
+    polyomino::pack(
+        space.grid.rows(),
+        space.grid.cols(),
+        shapes,
+        &space.required_presents,
+    )
+}
+
+const SPACE: &str = "a region like `12x5: 1 0 1 0 2 2`";
+
+fn parse_input(input: &str) -> Result<(Vec<Polyomino>, Vec<Space>)> {
+    const SHAPE: &str = "a present shape like `0:` followed by rows of `#` and `.`";
+    let mut shapes: Vec<Polyomino> = Vec::new();
+    let mut spaces: Vec<Space> = Vec::new();
+    let mut line_no = 0;
+
+    for block in input.split("\n\n") {
+        let mut lines = block.lines();
+        let first_line = line_no + 1;
+        line_no += block.lines().count() + 1;
+
+        let Some(header) = lines.next() else {
+            continue;
+        };
+        if header.contains('x') {
+            for (offset, line) in block.lines().enumerate() {
+                spaces.push(parse_space(first_line + offset, line, shapes.len())?);
+            }
+            continue;
+        }
+
+        utils::parse_value::<usize>(header.trim_end_matches(':'), first_line, SHAPE)?;
+        let mut cells = Vec::new();
+        for (r, row) in lines.enumerate() {
+            for (c, col) in row.chars().enumerate() {
+                match col {
+                    '#' => {
+                        cells.push((r, c));
+                    }
+                    '.' => {}
+                    _ => return Err(Error::parse(first_line + r + 1, SHAPE, row)),
+                }
+            }
+        }
+        shapes.push(Polyomino::new(cells));
+    }
+
+    if spaces.is_empty() {
+        return Err(Error::parse(line_no, SPACE, "end of input"));
+    }
+    Ok((shapes, spaces))
+}
+
+fn parse_space(line_no: usize, line: &str, shape_count: usize) -> Result<Space> {
+    let (dim, rest) = line
+        .split_once(':')
+        .ok_or_else(|| Error::parse(line_no, SPACE, line))?;
+    let (cols, rows) = dim
+        .split_once('x')
+        .ok_or_else(|| Error::parse(line_no, SPACE, line))?;
+    let required_presents = rest
+        .split_whitespace()
+        .map(|i| utils::parse_value(i, line_no, SPACE))
+        .collect::<Result<Vec<usize>>>()?;
+    if required_presents.len() != shape_count {
+        let expected = format!("a count for each of the {} shapes", shape_count);
+        return Err(Error::parse(line_no, expected, line));
+    }
+    let grid: Grid = (
+        utils::parse_value(rows, line_no, SPACE)?,
+        utils::parse_value(cols, line_no, SPACE)?,
+    )
+        .into();
+    Ok(Space {
+        grid,
+        required_presents,
+    })
+}
+
+/// Every space with its presents drawn where they fit, or a note that they
+/// don't, headed by its line of the input.
+pub fn draw_spaces(input: &str) -> Result<String> {
+    let (shapes, spaces) = parse_input(input)?;
+    let mut drawn = String::new();
+    for mut space in spaces {
+        let region = format!(
+            "{}x{}: {:?}",
+            space.grid.cols(),
+            space.grid.rows(),
+            space.required_presents
+        );
+        match pack(&space, &shapes) {
+            Some(packing) => {
+                packing.draw(&mut space.grid);
+                drawn.push_str(&format!("{}\n{}\n", region, space.grid));
+            }
+            None => drawn.push_str(&format!("{}\nthe presents don't fit\n\n", region)),
+        }
+    }
+    Ok(drawn)
+}
+
+pub struct Day12;
+
+impl Day for Day12 {
+    fn day(&self) -> u8 {
+        12
+    }
+
+    fn part1(&self, input: &str) -> Result<Answer> {
+        let (shapes, spaces) = parse_input(input)?;
+        Ok(spaces
+            .iter()
+            .filter(|space| pack(space, &shapes).is_some())
+            .count()
+            .into())
+    }
+
+    fn part2(&self, _input: &str) -> Result<Answer> {
+        // the last day only has one puzzle
+        Ok(0.into())
+    }
+}
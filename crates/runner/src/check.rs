@@ -0,0 +1,337 @@
+use crate::parallel;
+use aoc2025::day::{Alternative, Answer, Breakdown, Day};
+use aoc2025::error::Result;
+use std::collections::BTreeMap;
+
+/// Name the usual `part1`/`part2` solution is reported by.
+const MAIN: &str = "main";
+
+/// How the ways of solving one part compare.
+#[derive(Debug)]
+pub enum Outcome {
+    /// Every way of solving the part gave the same answer.
+    Agree {
+        names: Vec<&'static str>,
+        answer: Answer,
+    },
+    /// Two ways disagree, on the item on input line `line` if it's known,
+    /// with their answers, `None` for an item one of them left out.
+    Disagree {
+        line: Option<usize>,
+        first: (&'static str, Option<Answer>),
+        second: (&'static str, Option<Answer>),
+    },
+    /// One way failed or panicked.
+    Failed { name: &'static str, error: String },
+}
+
+/// An alternative left out of a check because something it needs is missing.
+#[derive(Debug)]
+pub struct Skipped {
+    pub name: &'static str,
+    pub reason: String,
+}
+
+/// Solve part `part` of `day` the usual way and with each of `alternatives`
+/// that can run here, comparing item by item where they break their answers
+/// down and then by their totals, and list the ones skipped. `source` names
+/// the input in errors.
+pub fn part(
+    day: &dyn Day,
+    part: u8,
+    alternatives: &[Alternative],
+    input: &str,
+    source: &str,
+) -> (Outcome, Vec<Skipped>) {
+    let mut runnable = Vec::new();
+    let mut skipped = Vec::new();
+    for alternative in alternatives {
+        match alternative.requires.map_or(Ok(()), |check| check()) {
+            Ok(()) => runnable.push(alternative),
+            Err(reason) => skipped.push(Skipped {
+                name: alternative.name,
+                reason,
+            }),
+        }
+    }
+    (compare(day, part, &runnable, input, source), skipped)
+}
+
+fn compare(
+    day: &dyn Day,
+    part: u8,
+    alternatives: &[&Alternative],
+    input: &str,
+    source: &str,
+) -> Outcome {
+    type Solve<'a> = Box<dyn Fn(&str) -> Result<Breakdown> + 'a>;
+    let main: Solve = Box::new(|input| match part {
+        1 => day.part1(input).map(Breakdown::from),
+        _ => day.part2(input).map(Breakdown::from),
+    });
+    let strategies = std::iter::once((MAIN, main)).chain(
+        alternatives
+            .iter()
+            .map(|alternative| (alternative.name, Box::new(alternative.solve) as Solve)),
+    );
+    let mut solved: Vec<(&'static str, Breakdown)> = Vec::new();
+    for (name, solve) in strategies {
+        match parallel::isolated(|| solve(input)) {
+            Ok(Ok(breakdown)) => solved.push((name, breakdown)),
+            Ok(Err(e)) => {
+                return Outcome::Failed {
+                    name,
+                    error: e.in_file(source).to_string(),
+                };
+            }
+            Err(panic) => {
+                return Outcome::Failed {
+                    name,
+                    error: format!("panicked: {}", panic),
+                };
+            }
+        }
+    }
+
+    if let Some(outcome) = first_item_discrepancy(&solved) {
+        return outcome;
+    }
+    let (reference, expected) = &solved[0];
+    if let Some((name, breakdown)) = solved[1..]
+        .iter()
+        .find(|(_, breakdown)| breakdown.total != expected.total)
+    {
+        return Outcome::Disagree {
+            line: None,
+            first: (reference, Some(expected.total.clone())),
+            second: (name, Some(breakdown.total.clone())),
+        };
+    }
+    Outcome::Agree {
+        names: solved.iter().map(|&(name, _)| name).collect(),
+        answer: expected.total.clone(),
+    }
+}
+
+/// The earliest line on which a way that breaks its answer down into items
+/// disagrees with the first one that does.
+fn first_item_discrepancy(solved: &[(&'static str, Breakdown)]) -> Option<Outcome> {
+    let mut itemised = solved
+        .iter()
+        .filter(|(_, breakdown)| !breakdown.items.is_empty());
+    let (reference, breakdown) = itemised.next()?;
+    let expected: BTreeMap<usize, &Answer> = breakdown
+        .items
+        .iter()
+        .map(|(line, answer)| (*line, answer))
+        .collect();
+
+    itemised
+        .filter_map(|(name, breakdown)| {
+            let actual: BTreeMap<usize, &Answer> = breakdown
+                .items
+                .iter()
+                .map(|(line, answer)| (*line, answer))
+                .collect();
+            let line = expected
+                .keys()
+                .chain(actual.keys())
+                .filter(|line| expected.get(line) != actual.get(line))
+                .min()?;
+            let outcome = Outcome::Disagree {
+                line: Some(*line),
+                first: (reference, expected.get(line).map(|&a| a.clone())),
+                second: (name, actual.get(line).map(|&a| a.clone())),
+            };
+            Some((*line, outcome))
+        })
+        .min_by_key(|&(line, _)| line)
+        .map(|(_, outcome)| outcome)
+}
+
+/// Print how part `part` of `day` compared, see [`describe`].
+pub fn print(day: u8, part: u8, outcome: &Outcome, skipped: &[Skipped], input: &str) {
+    print!("{}", describe(day, part, outcome, skipped, input));
+}
+
+/// How part `part` of `day` compared, quoting the input line of an item the
+/// ways disagree on, and which alternatives were skipped.
+fn describe(day: u8, part: u8, outcome: &Outcome, skipped: &[Skipped], input: &str) -> String {
+    let answer = |answer: &Option<Answer>| match answer {
+        Some(answer) => answer.to_string(),
+        None => "nothing".to_string(),
+    };
+    let mut text = format!("Day {:02} part {}: ", day, part);
+    match outcome {
+        Outcome::Agree { names, answer } => {
+            text += &format!("{} agree on {}\n", names.join(", "), answer)
+        }
+        Outcome::Disagree {
+            line,
+            first: (first, expected),
+            second: (second, actual),
+        } => {
+            let item = line.map_or(String::new(), |line| format!(" on line {}", line));
+            text += &format!(
+                "{} and {} disagree{}: {} vs {}\n",
+                first,
+                second,
+                item,
+                answer(expected),
+                answer(actual)
+            );
+            if let Some(quoted) = line.and_then(|line| input.lines().nth(line - 1)) {
+                text += &format!("  {}\n", quoted);
+            }
+        }
+        Outcome::Failed { name, error } => text += &format!("{} failed: {}\n", name, error),
+    }
+    for Skipped { name, reason } in skipped {
+        text += &format!("  {} skipped: {}\n", name, reason);
+    }
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc2025::error::Error;
+
+    const INPUT: &str = "1 2\n3 4\n5 6\n";
+
+    /// Sums the numbers on each line for part 1, and multiplies them for
+    /// part 2.
+    struct Lines;
+
+    fn line_sums(input: &str) -> Vec<(usize, Answer)> {
+        input
+            .lines()
+            .enumerate()
+            .map(|(idx, line)| {
+                let sum: u64 = line.split(' ').map(|n| n.parse::<u64>().unwrap()).sum();
+                (idx + 1, sum.into())
+            })
+            .collect()
+    }
+
+    fn total(items: &[(usize, Answer)]) -> u64 {
+        items
+            .iter()
+            .map(|(_, answer)| answer.to_string().parse::<u64>().unwrap())
+            .sum()
+    }
+
+    impl Day for Lines {
+        fn day(&self) -> u8 {
+            3
+        }
+
+        fn part1(&self, input: &str) -> Result<Answer> {
+            Ok(total(&line_sums(input)).into())
+        }
+
+        fn part2(&self, input: &str) -> Result<Answer> {
+            let product: u64 = input
+                .split_whitespace()
+                .map(|n| n.parse::<u64>().unwrap())
+                .product();
+            Ok(product.into())
+        }
+    }
+
+    fn alternative(
+        part: u8,
+        name: &'static str,
+        solve: fn(&str) -> Result<Breakdown>,
+    ) -> Alternative {
+        Alternative {
+            part,
+            name,
+            solve,
+            requires: None,
+        }
+    }
+
+    fn by_line(input: &str) -> Result<Breakdown> {
+        let items = line_sums(input);
+        Ok(Breakdown::new(total(&items), items))
+    }
+
+    /// Gets the second line wrong.
+    fn off_by_one(input: &str) -> Result<Breakdown> {
+        let mut items = line_sums(input);
+        items[1].1 = 8u64.into();
+        Ok(Breakdown::new(total(&items), items))
+    }
+
+    /// Leaves the last line out.
+    fn short(input: &str) -> Result<Breakdown> {
+        let mut items = line_sums(input);
+        items.pop();
+        Ok(Breakdown::new(total(&items), items))
+    }
+
+    fn report(alternatives: &[Alternative], part: u8) -> String {
+        let (outcome, skipped) = super::part(&Lines, part, alternatives, INPUT, "input");
+        describe(3, part, &outcome, &skipped, INPUT)
+    }
+
+    #[test]
+    fn agreement_names_every_way() {
+        let alternatives = [alternative(1, "by-line", by_line)];
+        assert_eq!(
+            report(&alternatives, 1),
+            "Day 03 part 1: main, by-line agree on 21\n"
+        );
+    }
+
+    #[test]
+    fn a_wrong_item_is_reported_with_its_line() {
+        let alternatives = [
+            alternative(1, "by-line", by_line),
+            alternative(1, "short", short),
+            alternative(1, "off-by-one", off_by_one),
+        ];
+        assert_eq!(
+            report(&alternatives, 1),
+            "Day 03 part 1: by-line and off-by-one disagree on line 2: 7 vs 8\n  3 4\n"
+        );
+        let alternatives = [
+            alternative(1, "by-line", by_line),
+            alternative(1, "short", short),
+        ];
+        assert_eq!(
+            report(&alternatives, 1),
+            "Day 03 part 1: by-line and short disagree on line 3: 11 vs nothing\n  5 6\n"
+        );
+    }
+
+    #[test]
+    fn a_wrong_total_is_reported_without_a_line() {
+        let alternatives = [alternative(2, "off-by-one", off_by_one)];
+        assert_eq!(
+            report(&alternatives, 2),
+            "Day 03 part 2: main and off-by-one disagree: 720 vs 22\n"
+        );
+    }
+
+    #[test]
+    fn failures_and_skips_are_reported() {
+        let mut missing = alternative(1, "external", by_line);
+        missing.requires = Some(|| Err("no solver installed".into()));
+        let alternatives = [
+            missing,
+            alternative(1, "broken", |_| Err(Error::parse(2, "a number", "x"))),
+        ];
+        assert_eq!(
+            report(&alternatives, 1),
+            "Day 03 part 1: broken failed: input:2: expected a number, found `x`\n  \
+             external skipped: no solver installed\n"
+        );
+        let alternatives = [alternative(1, "panicky", |_| panic!("oops"))];
+        assert_eq!(
+            report(&alternatives, 1),
+            "Day 03 part 1: panicky failed: panicked: oops\n"
+        );
+    }
+}
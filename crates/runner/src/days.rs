@@ -0,0 +1,46 @@
+use aoc2025::day::Day;
+use aoc2025::error::{Error, Result};
+
+/// Every day the runner knows about, in order.
+pub static DAYS: &[&dyn Day] = &[
+    &day01::Day01,
+    &day02::Day02,
+    &day03::Day03,
+    &day04::Day04,
+    &day05::Day05,
+    &day06::Day06,
+    &day07::Day07,
+    &day08::Day08,
+    &day09::Day09,
+    &day10::Day10,
+    &day11::Day11,
+    &day12::Day12,
+];
+
+pub fn find(day: u8) -> Result<&'static dyn Day> {
+    DAYS.iter()
+        .find(|d| d.day() == day)
+        .copied()
+        .ok_or_else(|| Error::Usage(format!("day {} is not registered with the runner", day)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn each_day_is_registered_once_in_order() {
+        let days: Vec<u8> = DAYS.iter().map(|day| day.day()).collect();
+        assert!(days.windows(2).all(|pair| pair[0] < pair[1]), "{:?}", days);
+        assert_eq!(days.first(), Some(&1));
+    }
+
+    #[test]
+    fn find_looks_days_up_by_number() {
+        for day in DAYS {
+            assert_eq!(find(day.day()).unwrap().day(), day.day());
+        }
+        assert!(matches!(find(0), Err(Error::Usage(_))));
+        assert!(matches!(find(26), Err(Error::Usage(_))));
+    }
+}
@@ -0,0 +1,90 @@
+use aoc2025::error::{Error, Result};
+use std::fs::{self, OpenOptions};
+use std::io::{ErrorKind, Write};
+use std::path::PathBuf;
+
+/// Example inputs from the puzzle text, kept as `examples/dayNN`, and their
+/// expected answers, kept as `dayNN part1 part2` lines in `examples/answers`
+/// with `?` for an answer that isn't filled in yet.
+pub struct Examples {
+    dir: PathBuf,
+}
+
+impl Examples {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Examples { dir: dir.into() }
+    }
+
+    pub fn input_path(&self, day: u8) -> PathBuf {
+        self.dir.join(format!("day{:02}", day))
+    }
+
+    fn answers_path(&self) -> PathBuf {
+        self.dir.join("answers")
+    }
+
+    /// The expected answers for `day`'s example, `None` where unknown.
+    pub fn expected(&self, day: u8) -> Result<[Option<String>; 2]> {
+        Ok(self.entry(day)?.unwrap_or_default())
+    }
+
+    fn entry(&self, day: u8) -> Result<Option<[Option<String>; 2]>> {
+        let path = self.answers_path();
+        let name = path.display().to_string();
+        let answers = match fs::read_to_string(&path) {
+            Ok(answers) => answers,
+            Err(e) if e.kind() == ErrorKind::NotFound => String::new(),
+            Err(source) => return Err(Error::Io { path: name, source }),
+        };
+
+        let key = format!("day{:02}", day);
+        for (idx, line) in answers.lines().enumerate() {
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            let [entry, part1, part2] = fields[..] else {
+                let expected = "`dayNN part1 part2`, with `?` for unknown answers";
+                return Err(Error::parse(idx + 1, expected, line).in_file(&name));
+            };
+            if entry == key {
+                let known = |answer: &str| (answer != "?").then(|| answer.to_string());
+                return Ok(Some([known(part1), known(part2)]));
+            }
+        }
+        Ok(None)
+    }
+
+    /// Create an empty example input and answers entry for `day`, whichever
+    /// of them it doesn't have yet.
+    pub fn add_entry(&self, day: u8) -> Result<()> {
+        let io_error = |path: PathBuf| {
+            move |source| Error::Io {
+                path: path.display().to_string(),
+                source,
+            }
+        };
+        fs::create_dir_all(&self.dir).map_err(io_error(self.dir.clone()))?;
+
+        let input = self.input_path(day);
+        let created = !input.exists();
+        if created {
+            fs::write(&input, "").map_err(io_error(input.clone()))?;
+        }
+        let add_answers = || -> Result<()> {
+            if self.entry(day)?.is_none() {
+                let path = self.answers_path();
+                let mut answers = OpenOptions::new()
+                    .create(true)
+                    .append(true)
+                    .open(&path)
+                    .map_err(io_error(path.clone()))?;
+                writeln!(answers, "day{:02} ? ?", day).map_err(io_error(path))?;
+            }
+            Ok(())
+        };
+        // Leave no input behind without its answers entry.
+        add_answers().inspect_err(|_| {
+            if created {
+                let _ = fs::remove_file(&input);
+            }
+        })
+    }
+}
@@ -0,0 +1,355 @@
+mod check;
+mod days;
+mod examples;
+mod new_day;
+mod parallel;
+mod report;
+
+use aoc2025::day::{Alternative, Answer, Day};
+use aoc2025::error::{Error, Result};
+use aoc2025::utils::fetch::{DEFAULT_YEAR, FetchConfig, HttpClient, InputCache};
+use aoc2025::utils::submit::{self, SubmissionHistory, Verdict};
+use aoc2025::utils::{self, InputSource};
+use check::Outcome;
+use clap::{Parser, Subcommand};
+use examples::Examples;
+use report::{Format, PartReport};
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use std::thread;
+use std::time::Instant;
+
+#[derive(Parser)]
+#[command(about = "Run Advent of Code solutions and submit their answers")]
+struct Cli {
+    /// Puzzle year, used when fetching inputs and submitting answers.
+    #[arg(long, default_value_t = DEFAULT_YEAR)]
+    year: u16,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Solve days against their cached inputs, fetching any that are missing.
+    Run {
+        /// Days to run, every registered day if none are given.
+        days: Vec<u8>,
+        /// Read the input from this path, or `-` for stdin, instead.
+        #[arg(long, conflicts_with = "example")]
+        input: Option<String>,
+        /// Use the example input and check the expected example answers.
+        #[arg(long)]
+        example: bool,
+        /// How to print the results.
+        #[arg(long, value_enum, default_value_t = Format::Text)]
+        format: Format,
+        /// Number of days to solve at once.
+        #[arg(short, long, default_value_t = 1, value_parser = clap::value_parser!(u16).range(1..))]
+        jobs: u16,
+        /// Also solve both parts of a day at once.
+        #[arg(long)]
+        parallel_parts: bool,
+    },
+    /// Compare each part's usual solution with the alternatives its day
+    /// registers, reporting the first place they disagree.
+    Check {
+        /// Days to check, every day with alternatives if none are given.
+        days: Vec<u8>,
+        /// Read the input from this path, or `-` for stdin, instead.
+        #[arg(long, conflicts_with = "example")]
+        input: Option<String>,
+        /// Use the example input.
+        #[arg(long)]
+        example: bool,
+    },
+    /// Submit an answer, solving the day first if no answer is given.
+    Submit {
+        day: u8,
+        #[arg(value_parser = clap::value_parser!(u8).range(1..=2))]
+        part: u8,
+        answer: Option<String>,
+    },
+    /// Create and register a crate for a new day.
+    NewDay { day: u8 },
+}
+
+/// Root of the workspace the runner was built in.
+fn workspace() -> PathBuf {
+    let runner = Path::new(env!("CARGO_MANIFEST_DIR"));
+    runner.ancestors().nth(2).unwrap_or(runner).to_path_buf()
+}
+
+/// How `run` solves the selected days and reports on them.
+#[derive(Clone, Copy)]
+struct RunOptions {
+    example: bool,
+    format: Format,
+    jobs: usize,
+    parallel_parts: bool,
+}
+
+struct Runner {
+    year: u16,
+    config: FetchConfig,
+    cache: InputCache<HttpClient>,
+}
+
+impl Runner {
+    fn new(year: u16) -> Result<Self> {
+        let config = FetchConfig::from_env()?;
+        let cache = InputCache::new(&config.cache_dir, HttpClient::new(&config));
+        Ok(Runner {
+            year,
+            config,
+            cache,
+        })
+    }
+
+    /// Read the input for `day`, from `input` if given or the cache otherwise.
+    fn read_input(&self, day: &dyn Day, input: Option<&str>) -> Result<(InputSource, String)> {
+        let source = match input {
+            Some(arg) => InputSource::from_arg(arg),
+            None => {
+                let path = self.cache.resolve(self.year, day.day())?;
+                InputSource::Path(path.display().to_string())
+            }
+        };
+        let input = utils::read_input(&source)?;
+        Ok((source, input))
+    }
+
+    fn solve_part(&self, day: &dyn Day, part: u8, input: Option<&str>) -> Result<Answer> {
+        let (source, input) = self.read_input(day, input)?;
+        let answer = match part {
+            1 => day.part1(&input),
+            _ => day.part2(&input),
+        };
+        answer.map_err(|e| e.in_file(source.name()))
+    }
+
+    /// Read the input for `day`, with the expected answers when checking
+    /// the example instead.
+    fn prepare(
+        &self,
+        day: &dyn Day,
+        input: Option<&str>,
+        examples: Option<&Examples>,
+    ) -> Result<(InputSource, String, [Option<String>; 2])> {
+        match examples {
+            Some(examples) => {
+                let path = examples.input_path(day.day()).display().to_string();
+                let (source, input) = self.read_input(day, Some(&path))?;
+                Ok((source, input, examples.expected(day.day())?))
+            }
+            None => {
+                let (source, input) = self.read_input(day, input)?;
+                Ok((source, input, [None, None]))
+            }
+        }
+    }
+
+    /// Solve both parts of `day`, reporting failures, panics included, rather
+    /// than returning them.
+    fn run_day(
+        &self,
+        day: &dyn Day,
+        input: Option<&str>,
+        examples: Option<&Examples>,
+        parallel_parts: bool,
+    ) -> Vec<PartReport> {
+        let mut reports = vec![PartReport::new(day.day(), 1), PartReport::new(day.day(), 2)];
+        let (source, input, expected) = match self.prepare(day, input, examples) {
+            Ok(prepared) => prepared,
+            Err(e) => {
+                reports.iter_mut().for_each(|report| report.fail(&e));
+                return reports;
+            }
+        };
+
+        let solve = |part| {
+            let start = Instant::now();
+            let answer = parallel::isolated(|| match part {
+                1 => day.part1(&input),
+                _ => day.part2(&input),
+            });
+            (start.elapsed().as_secs_f64() * 1000.0, answer)
+        };
+        let solved = if parallel_parts {
+            thread::scope(|scope| {
+                let part2 = scope.spawn(|| solve(2));
+                let part1 = solve(1);
+                [part1, part2.join().expect("panics are caught by the part")]
+            })
+        } else {
+            [solve(1), solve(2)]
+        };
+
+        for ((report, expected), (time_ms, answer)) in reports.iter_mut().zip(expected).zip(solved)
+        {
+            report.time_ms = time_ms;
+            match answer {
+                Ok(Ok(answer)) => report.record(answer, expected),
+                Ok(Err(e)) => report.fail(e.in_file(source.name())),
+                Err(panic) => report.fail(format!("panicked: {}", panic)),
+            }
+        }
+        reports
+    }
+
+    /// Solve `days`, `jobs` at a time, printing each day's reports in order.
+    /// Fails if any part failed or didn't match its expected answer.
+    fn run(&self, days: &[u8], input: Option<&str>, options: RunOptions) -> Result<ExitCode> {
+        let selected = select(days, input, |_| true)?;
+
+        let examples = options
+            .example
+            .then(|| Examples::new(workspace().join("examples")));
+        let mut failed = false;
+        parallel::ordered(
+            &selected,
+            options.jobs,
+            |day| self.run_day(*day, input, examples.as_ref(), options.parallel_parts),
+            |reports| {
+                failed |= reports.iter().any(PartReport::failed);
+                report::print(options.format, &reports);
+            },
+        );
+        Ok(if failed {
+            ExitCode::FAILURE
+        } else {
+            ExitCode::SUCCESS
+        })
+    }
+
+    /// Cross-check each part of `days` that has alternatives registered.
+    /// Fails if any way of solving a part fails or disagrees with another,
+    /// or a day's input can't be read, which doesn't stop the other days.
+    fn check(&self, days: &[u8], input: Option<&str>, example: bool) -> Result<ExitCode> {
+        let selected = select(days, input, |day| !day.alternatives().is_empty())?;
+        let examples = example.then(|| Examples::new(workspace().join("examples")));
+        let mut failed = false;
+        for day in selected {
+            let alternatives = day.alternatives();
+            if alternatives.is_empty() {
+                println!("Day {:02}: no alternatives registered", day.day());
+                continue;
+            }
+            let (source, input) = match self.prepare(day, input, examples.as_ref()) {
+                Ok((source, input, _)) => (source, input),
+                Err(e) => {
+                    failed = true;
+                    println!("Day {:02}: {}", day.day(), e);
+                    continue;
+                }
+            };
+            for part in 1..=2 {
+                let alternatives: Vec<Alternative> = alternatives
+                    .iter()
+                    .filter(|alternative| alternative.part == part)
+                    .copied()
+                    .collect();
+                if alternatives.is_empty() {
+                    continue;
+                }
+                let (outcome, skipped) =
+                    check::part(day, part, &alternatives, &input, source.name());
+                failed |= !matches!(outcome, Outcome::Agree { .. });
+                check::print(day.day(), part, &outcome, &skipped, &input);
+            }
+        }
+        Ok(if failed {
+            ExitCode::FAILURE
+        } else {
+            ExitCode::SUCCESS
+        })
+    }
+
+    fn submit(&self, day: u8, part: u8, answer: Option<String>) -> Result<()> {
+        let answer = match answer {
+            Some(answer) => answer,
+            None => {
+                let answer = self.solve_part(days::find(day)?, part, None)?;
+                println!("Day {:02} part {}: {}", day, part, answer);
+                answer.to_string()
+            }
+        };
+
+        let client = HttpClient::new(&self.config);
+        let mut history = SubmissionHistory::load(self.config.cache_dir.join("submissions.tsv"))?;
+        let verdict = submit::submit(&client, &mut history, (self.year, day, part), &answer)?;
+        match verdict {
+            Verdict::Correct => println!("`{}` is correct", answer),
+            Verdict::TooHigh => println!("`{}` is too high", answer),
+            Verdict::TooLow => println!("`{}` is too low", answer),
+            Verdict::Incorrect => println!("`{}` is not correct", answer),
+            Verdict::Wait(Some(seconds)) => println!("answered too recently, wait {}s", seconds),
+            Verdict::Wait(None) => println!("answered too recently, wait before retrying"),
+            Verdict::WrongLevel => println!("day {} part {} is solved or locked", day, part),
+            Verdict::Unknown => println!("unrecognised response, check the puzzle page"),
+        }
+        Ok(())
+    }
+}
+
+/// The registered `days`, or every registered day matching `default` if none
+/// are given. Only one day can be read from an explicit `input`.
+fn select(
+    days: &[u8],
+    input: Option<&str>,
+    default: impl Fn(&dyn Day) -> bool,
+) -> Result<Vec<&'static dyn Day>> {
+    let selected = if days.is_empty() {
+        days::DAYS
+            .iter()
+            .copied()
+            .filter(|&day| default(day))
+            .collect()
+    } else {
+        days.iter()
+            .map(|&day| days::find(day))
+            .collect::<Result<Vec<_>>>()?
+    };
+    if input.is_some() && selected.len() != 1 {
+        return Err(Error::Usage("--input needs exactly one day".into()));
+    }
+    Ok(selected)
+}
+
+fn main() -> Result<ExitCode> {
+    let cli = Cli::parse();
+    let runner = Runner::new(cli.year)?;
+    match cli.command {
+        Command::Run {
+            days,
+            input,
+            example,
+            format,
+            jobs,
+            parallel_parts,
+        } => {
+            let options = RunOptions {
+                example,
+                format,
+                jobs: jobs.into(),
+                parallel_parts,
+            };
+            runner.run(&days, input.as_deref(), options)
+        }
+        Command::Check {
+            days,
+            input,
+            example,
+        } => runner.check(&days, input.as_deref(), example),
+        Command::Submit { day, part, answer } => {
+            runner.submit(day, part, answer)?;
+            Ok(ExitCode::SUCCESS)
+        }
+        Command::NewDay { day } => {
+            let crate_dir = new_day::create(&workspace(), day)?;
+            println!("created {}", crate_dir.display());
+            Ok(ExitCode::SUCCESS)
+        }
+    }
+}
@@ -0,0 +1,293 @@
+use crate::examples::Examples;
+use aoc2025::error::{Error, Result};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const CARGO_TEMPLATE: &str = include_str!("../templates/Cargo.toml.template");
+const LIB_TEMPLATE: &str = include_str!("../templates/lib.rs.template");
+const MAIN_TEMPLATE: &str = include_str!("../templates/main.rs.template");
+
+fn io_error(path: &Path) -> impl FnOnce(std::io::Error) -> Error {
+    let path = path.display().to_string();
+    move |source| Error::Io { path, source }
+}
+
+fn render(template: &str, day: u8) -> String {
+    template
+        .replace("{crate}", &format!("day{:02}", day))
+        .replace("{name}", &format!("Day{:02}", day))
+        .replace("{day}", &day.to_string())
+}
+
+/// Create `crates/dayNN` from the templates, register it with the runner and
+/// add an empty example entry. Refuses to touch a day that already exists.
+pub fn create(workspace: &Path, day: u8) -> Result<PathBuf> {
+    if !(1..=25).contains(&day) {
+        return Err(Error::Usage(format!("new-day <day> with a day from 1 to 25, not {}", day)));
+    }
+    let crate_name = format!("day{:02}", day);
+    let crate_dir = workspace.join("crates").join(&crate_name);
+    if crate_dir.exists() {
+        return Err(Error::Usage(format!(
+            "new-day <day> for a day without a crate, {} already exists",
+            crate_dir.display()
+        )));
+    }
+
+    let runner_dir = workspace.join("crates").join("runner");
+    let manifest_path = runner_dir.join("Cargo.toml");
+    let days_path = runner_dir.join("src").join("days.rs");
+    let original_manifest = fs::read_to_string(&manifest_path).map_err(io_error(&manifest_path))?;
+    let original_days = fs::read_to_string(&days_path).map_err(io_error(&days_path))?;
+    let manifest = register_dependency(&original_manifest, &crate_name)
+        .map_err(|e| e.in_file(&manifest_path.display().to_string()))?;
+    let days = register_day(&original_days, day)
+        .map_err(|e| e.in_file(&days_path.display().to_string()))?;
+    let src_dir = crate_dir.join("src");
+    let files = [
+        (crate_dir.join("Cargo.toml"), CARGO_TEMPLATE),
+        (src_dir.join("lib.rs"), LIB_TEMPLATE),
+        (src_dir.join("main.rs"), MAIN_TEMPLATE),
+    ]
+    .map(|(path, template)| (path, render(template, day)));
+
+    // The runner only learns of the day once its crate is complete, and the
+    // example entry is added last of all. A failure on the way undoes every
+    // step before it, so that the workspace is never left with a
+    // half-registered day.
+    let undo = |manifest: bool, days: bool| {
+        if days {
+            let _ = fs::write(&days_path, &original_days);
+        }
+        if manifest {
+            let _ = fs::write(&manifest_path, &original_manifest);
+        }
+        let _ = fs::remove_dir_all(&crate_dir);
+    };
+    let scaffold = || -> Result<()> {
+        fs::create_dir_all(&src_dir).map_err(io_error(&src_dir))?;
+        for (path, contents) in &files {
+            fs::write(path, contents).map_err(io_error(path))?;
+        }
+        Ok(())
+    };
+    if let Err(e) = scaffold() {
+        undo(false, false);
+        return Err(e);
+    }
+    if let Err(e) = fs::write(&manifest_path, manifest) {
+        undo(false, false);
+        return Err(io_error(&manifest_path)(e));
+    }
+    if let Err(e) = fs::write(&days_path, days) {
+        undo(true, false);
+        return Err(io_error(&days_path)(e));
+    }
+    if let Err(e) = Examples::new(workspace.join("examples")).add_entry(day) {
+        undo(true, true);
+        return Err(e);
+    }
+    Ok(crate_dir)
+}
+
+/// Add a path dependency on the new day after the runner's existing days.
+fn register_dependency(manifest: &str, crate_name: &str) -> Result<String> {
+    let line = format!(
+        "{} = {{ version = \"0.1.0\", path = \"../{}\" }}",
+        crate_name, crate_name
+    );
+    let mut lines: Vec<&str> = manifest.lines().collect();
+    if lines.iter().any(|l| l.starts_with(&format!("{} =", crate_name))) {
+        return Err(Error::Usage(format!(
+            "new-day <day> for a day without a crate, {} is already a runner dependency",
+            crate_name
+        )));
+    }
+    let position = lines
+        .iter()
+        .rposition(|l| l.starts_with("day") && *l < line.as_str())
+        .map(|idx| idx + 1)
+        .or_else(|| lines.iter().position(|l| *l == "[dependencies]").map(|idx| idx + 1))
+        .ok_or_else(|| Error::parse(1, "a `[dependencies]` table", "none"))?;
+    lines.insert(position, &line);
+    Ok(lines.join("\n") + "\n")
+}
+
+/// Add the new day to the runner's `DAYS` list, after the days before it.
+fn register_day(days: &str, day: u8) -> Result<String> {
+    let entry = format!("    &day{:02}::Day{:02},", day, day);
+    if days.lines().any(|l| l == entry) {
+        return Err(Error::Usage(format!(
+            "new-day <day> for a day without a crate, day {} is already registered",
+            day
+        )));
+    }
+    let mut lines: Vec<&str> = days.lines().collect();
+    let start = lines
+        .iter()
+        .position(|l| l.starts_with("pub static DAYS"))
+        .ok_or_else(|| Error::parse(1, "a `pub static DAYS` list", "none"))?;
+    let end = lines[start..]
+        .iter()
+        .position(|l| *l == "];")
+        .map(|idx| start + idx)
+        .ok_or_else(|| Error::parse(start + 1, "the `DAYS` list to end with `];`", "none"))?;
+    let position = lines[start + 1..end]
+        .iter()
+        .rposition(|l| *l < entry.as_str())
+        .map_or(start + 1, |idx| start + 1 + idx + 1);
+    lines.insert(position, &entry);
+    Ok(lines.join("\n") + "\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MANIFEST: &str = "[package]
+name = \"runner\"
+
+[dependencies]
+aoc2025 = { path = \"../..\" }
+day01 = { version = \"0.1.0\", path = \"../day01\" }
+day03 = { version = \"0.1.0\", path = \"../day03\" }
+";
+
+    const DAYS: &str = "use aoc2025::day::Day;
+
+pub static DAYS: &[&dyn Day] = &[
+    &day01::Day01,
+    &day03::Day03,
+];
+
+pub fn find() {}
+";
+
+    /// A workspace of its own for the test called `name`, with a runner
+    /// that knows days 1 and 3.
+    fn workspace(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("runner-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        let src = dir.join("crates").join("runner").join("src");
+        fs::create_dir_all(&src).unwrap();
+        fs::write(src.parent().unwrap().join("Cargo.toml"), MANIFEST).unwrap();
+        fs::write(src.join("days.rs"), DAYS).unwrap();
+        dir
+    }
+
+    fn runner_files(workspace: &Path) -> (String, String) {
+        let runner = workspace.join("crates").join("runner");
+        (
+            fs::read_to_string(runner.join("Cargo.toml")).unwrap(),
+            fs::read_to_string(runner.join("src").join("days.rs")).unwrap(),
+        )
+    }
+
+    #[test]
+    fn dependencies_go_in_day_order() {
+        let manifest = register_dependency(MANIFEST, "day02").unwrap();
+        let lines: Vec<&str> = manifest.lines().collect();
+        assert_eq!(
+            lines[6],
+            "day02 = { version = \"0.1.0\", path = \"../day02\" }"
+        );
+        assert_eq!(
+            lines[7],
+            "day03 = { version = \"0.1.0\", path = \"../day03\" }"
+        );
+        let manifest = register_dependency(MANIFEST, "day13").unwrap();
+        assert!(manifest.ends_with("day13 = { version = \"0.1.0\", path = \"../day13\" }\n"));
+        let first = register_dependency("[dependencies]\nclap = \"4\"\n", "day01").unwrap();
+        assert_eq!(
+            first,
+            "[dependencies]\nday01 = { version = \"0.1.0\", path = \"../day01\" }\nclap = \"4\"\n"
+        );
+    }
+
+    #[test]
+    fn dependency_is_refused_twice_or_without_a_table() {
+        assert!(matches!(
+            register_dependency(MANIFEST, "day03"),
+            Err(Error::Usage(_))
+        ));
+        assert!(matches!(
+            register_dependency("[package]\n", "day01"),
+            Err(Error::Parse { .. })
+        ));
+    }
+
+    #[test]
+    fn days_go_in_order() {
+        let days = register_day(DAYS, 2).unwrap();
+        assert!(days.contains("    &day01::Day01,\n    &day02::Day02,\n    &day03::Day03,\n];"));
+        let days = register_day(DAYS, 13).unwrap();
+        assert!(days.contains("    &day03::Day03,\n    &day13::Day13,\n];\n\npub fn find"));
+        let empty = "pub static DAYS: &[&dyn Day] = &[\n];\n";
+        assert_eq!(
+            register_day(empty, 1).unwrap(),
+            "pub static DAYS: &[&dyn Day] = &[\n    &day01::Day01,\n];\n"
+        );
+    }
+
+    #[test]
+    fn day_is_refused_twice_or_without_a_list() {
+        assert!(matches!(register_day(DAYS, 3), Err(Error::Usage(_))));
+        assert!(matches!(
+            register_day("pub fn find() {}\n", 2),
+            Err(Error::Parse { line: 1, .. })
+        ));
+        assert!(matches!(
+            register_day("\npub static DAYS: &[&dyn Day] = &[\n", 2),
+            Err(Error::Parse { line: 2, .. })
+        ));
+    }
+
+    #[test]
+    fn create_scaffolds_and_registers_a_day() {
+        let workspace = workspace("create");
+        let crate_dir = create(&workspace, 2).unwrap();
+        assert_eq!(crate_dir, workspace.join("crates").join("day02"));
+        let lib = fs::read_to_string(crate_dir.join("src").join("lib.rs")).unwrap();
+        assert!(lib.contains("Day02"));
+        assert!(crate_dir.join("src").join("main.rs").exists());
+        assert!(crate_dir.join("Cargo.toml").exists());
+
+        let (manifest, days) = runner_files(&workspace);
+        assert_eq!(manifest, register_dependency(MANIFEST, "day02").unwrap());
+        assert_eq!(days, register_day(DAYS, 2).unwrap());
+        let examples = workspace.join("examples");
+        assert_eq!(fs::read_to_string(examples.join("day02")).unwrap(), "");
+        assert_eq!(
+            fs::read_to_string(examples.join("answers")).unwrap(),
+            "day02 ? ?\n"
+        );
+    }
+
+    #[test]
+    fn create_refuses_days_out_of_range_or_already_there() {
+        let workspace = workspace("refuse");
+        for day in [0, 26] {
+            assert!(matches!(create(&workspace, day), Err(Error::Usage(_))));
+        }
+        // Registered with the runner, but without a crate.
+        assert!(matches!(create(&workspace, 3), Err(Error::Usage(_))));
+        assert!(!workspace.join("crates").join("day03").exists());
+
+        let existing = workspace.join("crates").join("day04");
+        fs::create_dir_all(&existing).unwrap();
+        assert!(matches!(create(&workspace, 4), Err(Error::Usage(_))));
+        assert!(existing.exists());
+        assert_eq!(runner_files(&workspace), (MANIFEST.into(), DAYS.into()));
+        assert!(!workspace.join("examples").exists());
+    }
+
+    #[test]
+    fn failing_to_add_the_example_undoes_everything() {
+        let workspace = workspace("rollback");
+        // A file where the examples directory should be.
+        fs::write(workspace.join("examples"), "").unwrap();
+        assert!(matches!(create(&workspace, 2), Err(Error::Io { .. })));
+        assert!(!workspace.join("crates").join("day02").exists());
+        assert_eq!(runner_files(&workspace), (MANIFEST.into(), DAYS.into()));
+    }
+}
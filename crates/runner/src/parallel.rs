@@ -0,0 +1,138 @@
+use std::any::Any;
+use std::collections::BTreeMap;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc;
+use std::thread;
+
+/// Run `work` over `items` on up to `jobs` threads, handing each result to
+/// `emit` in the order of `items` as soon as it and everything before it is
+/// done.
+pub fn ordered<T: Sync, R: Send>(
+    items: &[T],
+    jobs: usize,
+    work: impl Fn(&T) -> R + Sync,
+    mut emit: impl FnMut(R),
+) {
+    let next = AtomicUsize::new(0);
+    let (sender, receiver) = mpsc::channel();
+    thread::scope(|scope| {
+        for _ in 0..jobs.clamp(1, items.len().max(1)) {
+            let (next, work, sender) = (&next, &work, sender.clone());
+            scope.spawn(move || {
+                loop {
+                    let idx = next.fetch_add(1, Ordering::Relaxed);
+                    let Some(item) = items.get(idx) else { break };
+                    if sender.send((idx, work(item))).is_err() {
+                        break;
+                    }
+                }
+            });
+        }
+        drop(sender);
+
+        let mut pending = BTreeMap::new();
+        let mut emitted = 0;
+        for (idx, result) in receiver {
+            pending.insert(idx, result);
+            while let Some(result) = pending.remove(&emitted) {
+                emit(result);
+                emitted += 1;
+            }
+        }
+    });
+}
+
+/// Run `f`, turning a panic into an error carrying the panic message so that
+/// it only fails the work it happened in.
+pub fn isolated<R>(f: impl FnOnce() -> R) -> Result<R, String> {
+    panic::catch_unwind(AssertUnwindSafe(f)).map_err(|payload| panic_message(&*payload))
+}
+
+fn panic_message(payload: &(dyn Any + Send)) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        message.to_string()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "unknown panic".to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+    use std::time::Duration;
+
+    #[test]
+    fn results_come_out_in_order_however_the_jobs_finish() {
+        let items: Vec<u64> = (0..8).collect();
+        let finished = Mutex::new(Vec::new());
+        let mut emitted = Vec::new();
+        ordered(
+            &items,
+            4,
+            |&item| {
+                // The earliest items take the longest.
+                thread::sleep(Duration::from_millis(10 * (8 - item)));
+                finished.lock().unwrap().push(item);
+                item * item
+            },
+            |result| emitted.push(result),
+        );
+        assert_eq!(emitted, [0, 1, 4, 9, 16, 25, 36, 49]);
+        let finished = finished.into_inner().unwrap();
+        assert_eq!(finished.len(), items.len());
+        assert!(!finished.is_sorted(), "{:?}", finished);
+    }
+
+    #[test]
+    fn any_number_of_jobs_works_through_every_item() {
+        for jobs in [0, 1, 3, 100] {
+            let mut emitted = Vec::new();
+            ordered(
+                &[1, 2, 3],
+                jobs,
+                |&item| item * 10,
+                |result| emitted.push(result),
+            );
+            assert_eq!(emitted, [10, 20, 30]);
+        }
+        let mut emitted: Vec<i32> = Vec::new();
+        ordered(&[], 4, |&item: &i32| item, |result| emitted.push(result));
+        assert!(emitted.is_empty());
+    }
+
+    #[test]
+    fn a_panicking_job_only_fails_itself() {
+        let mut emitted = Vec::new();
+        ordered(
+            &[1, 2, 3, 4],
+            2,
+            |&day| {
+                isolated(|| {
+                    if day == 2 {
+                        panic!("day {} broke", day);
+                    }
+                    day
+                })
+            },
+            |result| emitted.push(result),
+        );
+        assert_eq!(
+            emitted,
+            [Ok(1), Err("day 2 broke".to_string()), Ok(3), Ok(4)]
+        );
+    }
+
+    #[test]
+    fn panic_messages() {
+        assert_eq!(isolated(|| 5), Ok(5));
+        assert_eq!(isolated(|| panic!("plain")), Err::<(), _>("plain".into()));
+        assert_eq!(
+            isolated(|| panic::panic_any(7)),
+            Err::<(), _>("unknown panic".into())
+        );
+    }
+}
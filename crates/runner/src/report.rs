@@ -0,0 +1,157 @@
+use aoc2025::day::Answer;
+use clap::ValueEnum;
+use serde::Serialize;
+use std::fmt::Display;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Format {
+    /// Answers grouped by day, for people.
+    Text,
+    /// One JSON object per part, for scripts.
+    Json,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Status {
+    /// Solved, with nothing to check the answer against.
+    Ok,
+    /// Matches the expected example answer.
+    Correct,
+    /// Doesn't match the expected example answer.
+    Incorrect,
+    /// The input couldn't be read or the solution failed or panicked.
+    Error,
+}
+
+/// The outcome of solving one part of one day.
+#[derive(Debug, Clone, Serialize)]
+pub struct PartReport {
+    pub day: u8,
+    pub part: u8,
+    pub answer: Option<String>,
+    pub time_ms: f64,
+    pub status: Status,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expected: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl PartReport {
+    pub fn new(day: u8, part: u8) -> Self {
+        PartReport {
+            day,
+            part,
+            answer: None,
+            time_ms: 0.0,
+            status: Status::Ok,
+            expected: None,
+            error: None,
+        }
+    }
+
+    /// Record the answer, checking it against `expected` if there is one.
+    pub fn record(&mut self, answer: Answer, expected: Option<String>) {
+        self.status = match &expected {
+            Some(expected) if expected == answer.as_str() => Status::Correct,
+            Some(_) => Status::Incorrect,
+            None => Status::Ok,
+        };
+        self.answer = Some(answer.to_string());
+        self.expected = expected;
+    }
+
+    pub fn fail(&mut self, error: impl Display) {
+        self.status = Status::Error;
+        self.error = Some(error.to_string());
+    }
+
+    pub fn failed(&self) -> bool {
+        matches!(self.status, Status::Incorrect | Status::Error)
+    }
+}
+
+/// A report as one line of JSON, like
+/// `{"day":1,"part":2,"answer":"6","time_ms":0.25,"status":"ok"}`, with
+/// `expected` and `error` only where there are any.
+fn json(report: &PartReport) -> String {
+    serde_json::to_string(report).expect("reports always serialise")
+}
+
+/// Print the reports for one day.
+pub fn print(format: Format, reports: &[PartReport]) {
+    match format {
+        Format::Json => {
+            for report in reports {
+                println!("{}", json(report));
+            }
+        }
+        Format::Text => {
+            if let Some(first) = reports.first() {
+                println!("Day {:02}", first.day);
+            }
+            for report in reports {
+                let outcome = match (report.status, &report.answer, &report.error) {
+                    (Status::Error, _, Some(error)) => format!("error: {}", error),
+                    (_, Some(answer), _) => answer.clone(),
+                    _ => String::new(),
+                };
+                let mut notes = Vec::new();
+                match (report.status, &report.expected) {
+                    (Status::Correct, _) => notes.push("ok".to_string()),
+                    (Status::Incorrect, Some(expected)) => {
+                        notes.push(format!("expected {}", expected))
+                    }
+                    _ => {}
+                }
+                notes.push(format!("{:.2}ms", report.time_ms));
+                println!("  part {}: {} ({})", report.part, outcome, notes.join(", "));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn json_of_a_plain_answer() {
+        let mut report = PartReport::new(1, 2);
+        report.record(Answer::from(6usize), None);
+        report.time_ms = 0.25;
+        assert_eq!(
+            json(&report),
+            r#"{"day":1,"part":2,"answer":"6","time_ms":0.25,"status":"ok"}"#
+        );
+    }
+
+    #[test]
+    fn json_of_checked_answers() {
+        let mut report = PartReport::new(5, 1);
+        report.record(Answer::from(3usize), Some("3".into()));
+        assert_eq!(
+            json(&report),
+            r#"{"day":5,"part":1,"answer":"3","time_ms":0.0,"status":"correct","expected":"3"}"#
+        );
+        report.record(Answer::from(4usize), Some("3".into()));
+        report.time_ms = 12.0;
+        assert_eq!(
+            json(&report),
+            r#"{"day":5,"part":1,"answer":"4","time_ms":12.0,"status":"incorrect","expected":"3"}"#
+        );
+        assert!(report.failed());
+    }
+
+    #[test]
+    fn json_of_an_error() {
+        let mut report = PartReport::new(12, 1);
+        report.fail("inputs/day12:3: expected a region, found `\"x\"`");
+        assert_eq!(
+            json(&report),
+            r#"{"day":12,"part":1,"answer":null,"time_ms":0.0,"status":"error","error":"inputs/day12:3: expected a region, found `\"x\"`"}"#
+        );
+        assert!(report.failed());
+    }
+}
@@ -0,0 +1,370 @@
+use crate::grid::{Grid, GridPosition};
+use crate::point::Point2;
+use crate::rectangle::{self, Rectangle};
+use std::fmt::{Display, Formatter};
+
+/// `value` leaves no room for a border slot on one side of it, as it isn't
+/// within `i64::MIN + 1..=i64::MAX - 2`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OutOfRange {
+    pub value: i64,
+}
+
+impl Display for OutOfRange {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} leaves no room for a border to compress around", self.value)
+    }
+}
+
+/// The distinct values along one axis, each given a slot of its own, with a
+/// slot for each gap between neighbouring values and one more on either side
+/// as a border.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CompressedAxis {
+    /// The first coordinate in each slot, plus the end of the last slot.
+    starts: Vec<i64>,
+}
+
+impl CompressedAxis {
+    /// Fails on the smallest or largest value if there's no room for the
+    /// borders, see [`OutOfRange`].
+    pub fn new(values: impl IntoIterator<Item = i64>) -> Result<Self, OutOfRange> {
+        let mut values: Vec<i64> = values.into_iter().collect();
+        values.sort_unstable();
+        values.dedup();
+
+        let mut starts = Vec::with_capacity(values.len() * 2 + 2);
+        let (Some(&first), Some(&last)) = (values.first(), values.last()) else {
+            return Ok(CompressedAxis { starts });
+        };
+        starts.push(first.checked_sub(1).ok_or(OutOfRange { value: first })?);
+        // The border after the last value is a single coordinate wide.
+        let end = last.checked_add(2).ok_or(OutOfRange { value: last })?;
+        for (idx, &value) in values.iter().enumerate() {
+            starts.push(value);
+            if values.get(idx + 1).is_none_or(|&next| next > value + 1) {
+                starts.push(value + 1);
+            }
+        }
+        starts.push(end);
+        Ok(CompressedAxis { starts })
+    }
+
+    /// Number of slots.
+    pub fn len(&self) -> usize {
+        self.starts.len().saturating_sub(1)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// The slot containing `value`, `None` if it's beyond the borders.
+    pub fn slot(&self, value: i64) -> Option<usize> {
+        let slot = self.starts.partition_point(|&start| start <= value);
+        (1..self.starts.len()).contains(&slot).then(|| slot - 1)
+    }
+
+    /// The first coordinate in `slot`.
+    pub fn start(&self, slot: usize) -> i64 {
+        self.starts[slot]
+    }
+
+    /// Number of coordinates in `slot`.
+    pub fn width(&self, slot: usize) -> u64 {
+        self.starts[slot].abs_diff(self.starts[slot + 1])
+    }
+
+    /// Number of coordinates in each slot.
+    pub fn widths(&self) -> Vec<u64> {
+        (0..self.len()).map(|slot| self.width(slot)).collect()
+    }
+
+    /// Number of coordinates from the start of `first` to the end of `last`.
+    pub fn span(&self, first: usize, last: usize) -> u64 {
+        let (first, last) = (first.min(last), first.max(last));
+        self.starts[first].abs_diff(self.starts[last + 1])
+    }
+}
+
+/// A plane compressed to a compact [`Grid`] along both axes, see
+/// [`CompressedAxis`]. Grid rows are `y` slots and columns `x` slots, and a
+/// cell stands for every point in its slots.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CompressedPlane {
+    pub xs: CompressedAxis,
+    pub ys: CompressedAxis,
+}
+
+impl CompressedPlane {
+    /// Compress around the coordinates of `points`. Fails on a coordinate
+    /// with no room for the borders beyond it, see [`OutOfRange`].
+    pub fn new(points: &[Point2<i64>]) -> Result<Self, OutOfRange> {
+        Ok(CompressedPlane {
+            xs: CompressedAxis::new(points.iter().map(|p| p.x))?,
+            ys: CompressedAxis::new(points.iter().map(|p| p.y))?,
+        })
+    }
+
+    pub fn rows(&self) -> usize {
+        self.ys.len()
+    }
+
+    pub fn cols(&self) -> usize {
+        self.xs.len()
+    }
+
+    /// The cell containing `point`, `None` if it's beyond the borders.
+    pub fn position(&self, point: Point2<i64>) -> Option<GridPosition> {
+        Some(GridPosition(self.ys.slot(point.y)?, self.xs.slot(point.x)?))
+    }
+
+    /// The first point in a cell.
+    pub fn point(&self, position: GridPosition) -> Point2<i64> {
+        Point2::new(self.xs.start(position.1), self.ys.start(position.0))
+    }
+
+    /// Number of points a cell stands for.
+    pub fn cell_area(&self, position: GridPosition) -> u128 {
+        u128::from(self.ys.width(position.0)) * u128::from(self.xs.width(position.1))
+    }
+
+    /// Number of points in the block of cells with opposite corners `a`
+    /// and `b`.
+    pub fn area(&self, a: GridPosition, b: GridPosition) -> u128 {
+        u128::from(self.ys.span(a.0, b.0)) * u128::from(self.xs.span(a.1, b.1))
+    }
+
+    /// A grid with each cell set from the first point it stands for.
+    pub fn grid_with(&self, value: impl Fn(Point2<i64>) -> char) -> Grid {
+        let grid = (0..self.rows())
+            .map(|row| {
+                (0..self.cols())
+                    .map(|col| value(self.point(GridPosition(row, col))))
+                    .collect()
+            })
+            .collect();
+        Grid::new(grid, (self.rows(), self.cols()))
+    }
+
+    /// The first point of the top left cell and the last point of the
+    /// bottom right cell of a rectangle on a grid over this plane.
+    pub fn corners(&self, rectangle: &Rectangle) -> (Point2<i64>, Point2<i64>) {
+        let (top_left, bottom_right) = (rectangle.top_left, rectangle.bottom_right);
+        let last = |axis: &CompressedAxis, slot: usize| axis.start(slot + 1) - 1;
+        (
+            self.point(top_left),
+            Point2::new(
+                last(&self.xs, bottom_right.1),
+                last(&self.ys, bottom_right.0),
+            ),
+        )
+    }
+
+    /// The largest rectangle of cells whose value is `allowed` in a grid over
+    /// this plane, with its area in points.
+    pub fn largest_rectangle(
+        &self,
+        grid: &Grid,
+        allowed: impl Fn(char) -> bool,
+    ) -> Option<Rectangle> {
+        rectangle::largest_weighted_rectangle(grid, allowed, &self.ys.widths(), &self.xs.widths())
+    }
+
+    /// The largest rectangle of cells whose value is `allowed` in a grid over
+    /// this plane with opposite corners at two of `corners`, with its area in
+    /// points. Corners beyond the borders are ignored.
+    pub fn largest_cornered_rectangle(
+        &self,
+        grid: &Grid,
+        allowed: impl Fn(char) -> bool,
+        corners: &[Point2<i64>],
+    ) -> Option<Rectangle> {
+        let corners: Vec<GridPosition> = corners
+            .iter()
+            .filter_map(|&corner| self.position(corner))
+            .collect();
+        rectangle::largest_cornered_rectangle(
+            grid,
+            allowed,
+            &corners,
+            &self.ys.widths(),
+            &self.xs.widths(),
+        )
+    }
+
+    /// Prefix sums over `grid` of the number of points in cells whose value
+    /// matches `predicate`.
+    pub fn area_sums(&self, grid: &Grid, predicate: impl Fn(char) -> bool) -> PrefixSums {
+        PrefixSums::new(grid.rows(), grid.cols(), |position| {
+            match grid.get_cell(position) {
+                Some(cell) if predicate(cell.value) => self.cell_area(position),
+                _ => 0,
+            }
+        })
+    }
+}
+
+/// 2D prefix sums of a weight per grid cell, for summing any block of cells
+/// in constant time.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PrefixSums {
+    /// `sums[r][c]` is the total weight of the cells above and left of
+    /// `(r, c)`, exclusive.
+    sums: Vec<Vec<u128>>,
+}
+
+impl PrefixSums {
+    pub fn new(rows: usize, cols: usize, weight: impl Fn(GridPosition) -> u128) -> Self {
+        let mut sums = vec![vec![0; cols + 1]; rows + 1];
+        for row in 0..rows {
+            for col in 0..cols {
+                sums[row + 1][col + 1] =
+                    weight(GridPosition(row, col)) + sums[row][col + 1] + sums[row + 1][col]
+                        - sums[row][col];
+            }
+        }
+        PrefixSums { sums }
+    }
+
+    /// Total weight of the block of cells with opposite corners `a` and `b`.
+    pub fn sum(&self, a: GridPosition, b: GridPosition) -> u128 {
+        let (top, bottom) = (a.0.min(b.0), a.0.max(b.0) + 1);
+        let (left, right) = (a.1.min(b.1), a.1.max(b.1) + 1);
+        self.sums[bottom][right] + self.sums[top][left]
+            - self.sums[top][right]
+            - self.sums[bottom][left]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn empty_axis() {
+        let axis = CompressedAxis::new([]).unwrap();
+        assert!(axis.is_empty());
+        assert_eq!(axis.slot(0), None);
+        assert!(axis.widths().is_empty());
+    }
+
+    #[test]
+    fn single_value_gets_a_border_either_side() {
+        let axis = CompressedAxis::new([5]).unwrap();
+        assert_eq!(axis.len(), 3);
+        assert_eq!(axis.widths(), [1, 1, 1]);
+        assert_eq!(axis.slot(4), Some(0));
+        assert_eq!(axis.slot(5), Some(1));
+        assert_eq!(axis.slot(6), Some(2));
+        assert_eq!(axis.slot(3), None);
+        assert_eq!(axis.slot(7), None);
+    }
+
+    #[test]
+    fn gaps_get_a_slot_of_their_own() {
+        let axis = CompressedAxis::new([5, 1, 2, 5]).unwrap();
+        let starts: Vec<i64> = (0..axis.len()).map(|slot| axis.start(slot)).collect();
+        assert_eq!(starts, [0, 1, 2, 3, 5, 6]);
+        assert_eq!(axis.widths(), [1, 1, 1, 2, 1, 1]);
+        assert_eq!(axis.slot(4), Some(3));
+        assert_eq!(axis.span(3, 4), 3);
+        assert_eq!(axis.span(5, 0), 7);
+    }
+
+    #[test]
+    fn every_coordinate_between_the_borders_is_in_one_slot() {
+        let values = [-7, -3, -2, 0, 9, 10, 11, 40];
+        let axis = CompressedAxis::new(values).unwrap();
+        assert_eq!(axis.widths().iter().sum::<u64>(), 40 + 7 + 3);
+        for value in -8..=41 {
+            let slot = axis.slot(value).unwrap();
+            assert!(axis.start(slot) <= value);
+            assert!(value < axis.start(slot) + axis.width(slot) as i64);
+            if values.contains(&value) {
+                assert_eq!(axis.width(slot), 1);
+            }
+        }
+        assert_eq!(axis.slot(-9), None);
+        assert_eq!(axis.slot(42), None);
+    }
+
+    #[test]
+    fn values_need_room_for_the_borders() {
+        let (min, max) = (i64::MIN + 1, i64::MAX - 2);
+        let axis = CompressedAxis::new([max, min, max - 1]).unwrap();
+        let starts: Vec<i64> = (0..=axis.len()).map(|slot| axis.start(slot)).collect();
+        assert_eq!(
+            starts,
+            [i64::MIN, min, min + 1, max - 1, max, max + 1, i64::MAX]
+        );
+        assert_eq!(axis.span(0, axis.len() - 1), u64::MAX);
+        assert_eq!(axis.slot(i64::MIN), Some(0));
+        assert_eq!(axis.slot(i64::MAX), None);
+
+        for value in [i64::MIN, i64::MAX - 1, i64::MAX] {
+            assert_eq!(CompressedAxis::new([0, value]), Err(OutOfRange { value }));
+        }
+        let plane = CompressedPlane::new(&[Point2::new(0, 0), Point2::new(1, i64::MIN)]);
+        assert_eq!(plane, Err(OutOfRange { value: i64::MIN }));
+    }
+
+    #[test]
+    fn plane_cells_stand_for_their_points() {
+        let points = [Point2::new(1, 10), Point2::new(4, 10), Point2::new(4, 12)];
+        let plane = CompressedPlane::new(&points).unwrap();
+        assert_eq!((plane.rows(), plane.cols()), (5, 5));
+        let corner = plane.position(Point2::new(4, 12)).unwrap();
+        assert_eq!(corner, GridPosition(3, 3));
+        assert_eq!(plane.point(corner), Point2::new(4, 12));
+        let gap = plane.position(Point2::new(3, 11)).unwrap();
+        assert_eq!(gap, GridPosition(2, 2));
+        assert_eq!(plane.point(gap), Point2::new(2, 11));
+        assert_eq!(plane.cell_area(gap), 2);
+        assert_eq!(plane.area(GridPosition(0, 0), GridPosition(4, 4)), 5 * 6);
+        assert_eq!(plane.position(Point2::new(6, 10)), None);
+
+        let rectangle = Rectangle {
+            top_left: GridPosition(1, 1),
+            bottom_right: GridPosition(2, 2),
+            area: 0,
+        };
+        assert_eq!(
+            plane.corners(&rectangle),
+            (Point2::new(1, 10), Point2::new(3, 11))
+        );
+    }
+
+    #[test]
+    fn grids_are_filled_from_the_first_point_of_each_cell() {
+        let plane = CompressedPlane::new(&[Point2::new(0, 0), Point2::new(3, 0)]).unwrap();
+        let grid = plane.grid_with(|point| if point.x > 0 && point.x < 3 { '#' } else { '.' });
+        assert_eq!((grid.rows(), grid.cols()), (3, 5));
+        for row in 0..3 {
+            let cells: String = (0..5)
+                .map(|col| grid.get_cell(GridPosition(row, col)).unwrap().value)
+                .collect();
+            assert_eq!(cells, "..#..");
+        }
+    }
+
+    #[test]
+    fn prefix_sums_match_adding_up_blocks() {
+        let weight = |position: GridPosition| (position.0 * 7 + position.1 * 3) as u128 % 5;
+        let sums = PrefixSums::new(4, 6, weight);
+        for top in 0..4 {
+            for bottom in top..4 {
+                for left in 0..6 {
+                    for right in left..6 {
+                        let expected: u128 = (top..=bottom)
+                            .flat_map(|row| (left..=right).map(move |col| GridPosition(row, col)))
+                            .map(weight)
+                            .sum();
+                        let (a, b) = (GridPosition(top, right), GridPosition(bottom, left));
+                        assert_eq!(sums.sum(a, b), expected);
+                    }
+                }
+            }
+        }
+    }
+}
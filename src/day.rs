@@ -0,0 +1,120 @@
+use crate::error::Result;
+use crate::utils;
+use std::fmt::{Debug, Display, Formatter};
+
+/// The answer to one part of a puzzle, printed exactly as it would be
+/// submitted.
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct Answer(String);
+
+impl Answer {
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Display for Answer {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl Debug for Answer {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+macro_rules! answer_from {
+    ($($t:ty),*) => {
+        $(impl From<$t> for Answer {
+            fn from(value: $t) -> Self {
+                Answer(value.to_string())
+            }
+        })*
+    };
+}
+
+answer_from!(i32, i64, i128, u32, u64, u128, usize, isize, String, &str);
+
+/// Both answers for a day.
+#[derive(Debug, Clone)]
+pub struct Solution {
+    pub part1: Answer,
+    pub part2: Answer,
+}
+
+/// An answer along with the answer for each item of the input it's made up
+/// from, like each machine or range, so that two ways of solving a part can
+/// be compared item by item.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Breakdown {
+    pub total: Answer,
+    /// Each item's answer, by the 1-based input line the item is on.
+    pub items: Vec<(usize, Answer)>,
+}
+
+impl Breakdown {
+    pub fn new(total: impl Into<Answer>, items: Vec<(usize, Answer)>) -> Self {
+        Breakdown {
+            total: total.into(),
+            items,
+        }
+    }
+}
+
+impl From<Answer> for Breakdown {
+    fn from(total: Answer) -> Self {
+        Breakdown {
+            total,
+            items: Vec::new(),
+        }
+    }
+}
+
+/// Another way of solving one part of a day, to cross-check against the
+/// usual one.
+#[derive(Clone, Copy)]
+pub struct Alternative {
+    pub part: u8,
+    /// Short name to report the alternative by, like `bfs`.
+    pub name: &'static str,
+    pub solve: fn(&str) -> Result<Breakdown>,
+    /// Checks for something the alternative needs outside the crate, like
+    /// an external program, saying what's missing if it isn't there.
+    /// `runner check` skips the alternative rather than failing then.
+    pub requires: Option<fn() -> std::result::Result<(), String>>,
+}
+
+/// A day's puzzle, solved one part at a time from the raw input.
+pub trait Day: Sync {
+    /// Day of the month this puzzle was released.
+    fn day(&self) -> u8;
+
+    fn part1(&self, input: &str) -> Result<Answer>;
+
+    fn part2(&self, input: &str) -> Result<Answer>;
+
+    /// Other ways of solving the parts, compared with [`Day::part1`] and
+    /// [`Day::part2`] and with each other by `runner check`.
+    fn alternatives(&self) -> Vec<Alternative> {
+        Vec::new()
+    }
+
+    fn solve(&self, input: &str) -> Result<Solution> {
+        Ok(Solution {
+            part1: self.part1(input)?,
+            part2: self.part2(input)?,
+        })
+    }
+}
+
+/// Entry point for a day's own binary: solve the input named on the command
+/// line and print the solution.
+pub fn run(day: &dyn Day) -> Result<()> {
+    let source = utils::input_source()?;
+    let input = utils::read_input(&source)?;
+    let solution = day.solve(&input).map_err(|e| e.in_file(source.name()))?;
+    println!("{:?}", solution);
+    Ok(())
+}
@@ -0,0 +1,341 @@
+/// How many times a block of digits has to repeat, see [`repeated_blocks`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Repetitions {
+    Exactly(u32),
+    AtLeast(u32),
+}
+
+/// Which end of the order [`pick_digits`] picks from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Pick {
+    Largest,
+    Smallest,
+}
+
+/// How many numbers matched, and their total.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Tally {
+    pub count: u128,
+    pub sum: u128,
+}
+
+/// Number of digits of `n` in `base`, 1 for zero.
+pub fn digit_count(n: u64, base: u32) -> u32 {
+    let mut count = 1;
+    let mut n = n / u64::from(base);
+    while n > 0 {
+        count += 1;
+        n /= u64::from(base);
+    }
+    count
+}
+
+/// The value of `digits`, most significant first, in `base`. `None` if it
+/// doesn't fit in a `u128`.
+pub fn from_digits(digits: &[u8], base: u32) -> Option<u128> {
+    digits.iter().try_fold(0u128, |value, &digit| {
+        value
+            .checked_mul(u128::from(base))?
+            .checked_add(u128::from(digit))
+    })
+}
+
+/// The largest or smallest number that can be made by keeping `k` of
+/// `digits` in their original order, i.e. the lexicographically largest or
+/// smallest subsequence of length `k`. `None` if there are fewer than `k`
+/// digits.
+///
+/// Uses a monotonic stack: a digit is dropped whenever a better one follows
+/// it and there are still digits to spare, so each digit is pushed and popped
+/// at most once.
+pub fn pick_digits(digits: &[u8], k: usize, pick: Pick) -> Option<Vec<u8>> {
+    let mut spare = digits.len().checked_sub(k)?;
+    let mut kept: Vec<u8> = Vec::with_capacity(digits.len());
+    for &digit in digits {
+        while spare > 0
+            && let Some(&last) = kept.last()
+            && match pick {
+                Pick::Largest => last < digit,
+                Pick::Smallest => last > digit,
+            }
+        {
+            kept.pop();
+            spare -= 1;
+        }
+        kept.push(digit);
+    }
+    kept.truncate(k);
+    Some(kept)
+}
+
+/// Count and sum the positive numbers from `start` to `end` whose digits in
+/// `base` are one block repeated the given number of times, e.g. 123123 is
+/// 123 repeated twice and 1111 is 1 four times or 11 twice.
+///
+/// Works without visiting the numbers: for each length L and block length d,
+/// the matches are exactly the multiples of the repunit-style multiplier
+/// `(base^L - 1) / (base^d - 1)` by a d-digit block. "At least" sums the
+/// blocks over the divisors of L, using Möbius inversion so that a number
+/// repeating several ways, like 1111, is only counted once.
+pub fn repeated_blocks(start: u64, end: u64, base: u32, repetitions: Repetitions) -> Tally {
+    assert!(base >= 2, "base must be at least 2, got {}", base);
+    if start > end {
+        return Tally::default();
+    }
+
+    let mut count: i128 = 0;
+    let mut sum: i128 = 0;
+    let wide = u128::from(base);
+    for len in digit_count(start, base)..=digit_count(end, base) {
+        let lo = u128::from(start).max(wide.pow(len - 1));
+        let hi = u128::from(end).min(wide.pow(len) - 1);
+        if lo > hi {
+            continue;
+        }
+        let mut add = |block: u32, sign: i128| {
+            let (c, s) = blocks_of_len(lo, hi, wide, len, block);
+            count += sign * c as i128;
+            sum += sign * s as i128;
+        };
+        match repetitions {
+            Repetitions::Exactly(times) => {
+                if times > 0 && len.is_multiple_of(times) {
+                    add(len / times, 1);
+                }
+            }
+            Repetitions::AtLeast(times) => {
+                // A number with smallest block p repeats len / p times, and
+                // is counted for every block length p divides.
+                for primitive in divisors(len).filter(|p| len / p >= times.max(1)) {
+                    for block in divisors(primitive) {
+                        let sign = mobius(primitive / block);
+                        if sign != 0 {
+                            add(block, sign);
+                        }
+                    }
+                }
+            }
+        }
+    }
+    Tally {
+        count: count as u128,
+        sum: sum as u128,
+    }
+}
+
+/// Count and sum the `len` digit numbers from `lo` to `hi` that are a `block`
+/// digit block repeated, `block` dividing `len`.
+fn blocks_of_len(lo: u128, hi: u128, base: u128, len: u32, block: u32) -> (u128, u128) {
+    let multiplier = (base.pow(len) - 1) / (base.pow(block) - 1);
+    let first = base.pow(block - 1).max(lo.div_ceil(multiplier));
+    let last = (base.pow(block) - 1).min(hi / multiplier);
+    if first > last {
+        return (0, 0);
+    }
+    let count = last - first + 1;
+    let blocks = if count.is_multiple_of(2) {
+        (first + last) * (count / 2)
+    } else {
+        (first + last) / 2 * count
+    };
+    (count, blocks * multiplier)
+}
+
+fn divisors(n: u32) -> impl Iterator<Item = u32> {
+    (1..=n).filter(move |d| n.is_multiple_of(*d))
+}
+
+fn mobius(mut n: u32) -> i128 {
+    let mut sign = 1;
+    let mut p = 2;
+    while p * p <= n {
+        if n.is_multiple_of(p) {
+            n /= p;
+            if n.is_multiple_of(p) {
+                return 0;
+            }
+            sign = -sign;
+        }
+        p += 1;
+    }
+    if n > 1 { -sign } else { sign }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn digits(s: &str) -> Vec<u8> {
+        s.bytes().map(|b| b - b'0').collect()
+    }
+
+    /// The digits of `n` in `base`, most significant first.
+    fn to_digits(mut n: u64, base: u32) -> Vec<u8> {
+        let mut digits = vec![(n % u64::from(base)) as u8];
+        n /= u64::from(base);
+        while n > 0 {
+            digits.push((n % u64::from(base)) as u8);
+            n /= u64::from(base);
+        }
+        digits.reverse();
+        digits
+    }
+
+    /// Whether `n` is a block of digits repeated exactly `times` times.
+    fn repeats(n: u64, base: u32, times: usize) -> bool {
+        let digits = to_digits(n, base);
+        digits.len().is_multiple_of(times)
+            && digits
+                .chunks(digits.len() / times)
+                .all(|chunk| chunk == &digits[..digits.len() / times])
+    }
+
+    fn tally(numbers: impl Iterator<Item = u64>) -> Tally {
+        numbers.fold(Tally::default(), |tally, n| Tally {
+            count: tally.count + 1,
+            sum: tally.sum + u128::from(n),
+        })
+    }
+
+    #[test]
+    fn digit_counts() {
+        assert_eq!(digit_count(0, 10), 1);
+        assert_eq!(digit_count(9, 10), 1);
+        assert_eq!(digit_count(10, 10), 2);
+        assert_eq!(digit_count(u64::MAX, 10), 20);
+        assert_eq!(digit_count(8, 2), 4);
+        assert_eq!(digit_count(u64::MAX, 2), 64);
+    }
+
+    #[test]
+    fn values_from_digits() {
+        assert_eq!(from_digits(&[], 10), Some(0));
+        assert_eq!(from_digits(&digits("0042"), 10), Some(42));
+        assert_eq!(from_digits(&[1, 0, 1], 2), Some(5));
+        assert_eq!(from_digits(&[9; 38], 10), Some(10u128.pow(38) - 1));
+        assert_eq!(from_digits(&[9; 39], 10), None);
+    }
+
+    #[test]
+    fn picking_digits_keeps_their_order() {
+        let bank = digits("818181911112111");
+        assert_eq!(pick_digits(&bank, 2, Pick::Largest), Some(digits("92")));
+        assert_eq!(
+            pick_digits(&bank, 12, Pick::Largest),
+            Some(digits("888911112111"))
+        );
+        assert_eq!(pick_digits(&bank, 2, Pick::Smallest), Some(digits("11")));
+        assert_eq!(pick_digits(&bank, 4, Pick::Smallest), Some(digits("1111")));
+        assert_eq!(
+            pick_digits(&bank, bank.len(), Pick::Largest),
+            Some(bank.clone())
+        );
+        assert_eq!(pick_digits(&bank, bank.len() + 1, Pick::Largest), None);
+        assert_eq!(pick_digits(&bank, 0, Pick::Largest), Some(vec![]));
+        assert_eq!(pick_digits(&[], 0, Pick::Smallest), Some(vec![]));
+    }
+
+    #[test]
+    fn picking_digits_matches_trying_every_subsequence() {
+        let bank = digits("3141592653");
+        for k in 0..=bank.len() {
+            let subsequences = (0u32..1 << bank.len())
+                .filter(|mask| mask.count_ones() as usize == k)
+                .map(|mask| {
+                    (0..bank.len())
+                        .filter(|i| mask & (1 << i) != 0)
+                        .map(|i| bank[i])
+                        .collect::<Vec<u8>>()
+                });
+            let (min, max) = (subsequences.clone().min(), subsequences.max());
+            assert_eq!(pick_digits(&bank, k, Pick::Smallest), min);
+            assert_eq!(pick_digits(&bank, k, Pick::Largest), max);
+        }
+    }
+
+    #[test]
+    fn repeated_blocks_in_small_ranges() {
+        let twice = repeated_blocks(11, 22, 10, Repetitions::Exactly(2));
+        assert_eq!(twice, Tally { count: 2, sum: 33 });
+        let at_least = repeated_blocks(95, 115, 10, Repetitions::AtLeast(2));
+        assert_eq!(
+            at_least,
+            Tally {
+                count: 2,
+                sum: 99 + 111
+            }
+        );
+        // 1111 is 1 four times and 11 twice, but only counted once.
+        let ones = repeated_blocks(1111, 1111, 10, Repetitions::AtLeast(2));
+        assert_eq!(
+            ones,
+            Tally {
+                count: 1,
+                sum: 1111
+            }
+        );
+    }
+
+    #[test]
+    fn empty_and_single_value_ranges() {
+        let empty = Tally::default();
+        assert_eq!(repeated_blocks(22, 11, 10, Repetitions::Exactly(2)), empty);
+        assert_eq!(repeated_blocks(12, 12, 10, Repetitions::AtLeast(2)), empty);
+        assert_eq!(
+            repeated_blocks(7, 7, 10, Repetitions::AtLeast(1)),
+            Tally { count: 1, sum: 7 }
+        );
+        assert_eq!(repeated_blocks(0, 0, 10, Repetitions::AtLeast(1)), empty);
+        assert_eq!(repeated_blocks(1, 100, 10, Repetitions::Exactly(0)), empty);
+    }
+
+    #[test]
+    fn repeated_blocks_match_checking_each_number() {
+        for base in [2, 3, 10] {
+            for (start, end) in [(1, 3000), (998, 1012), (4321, 4321)] {
+                for times in 1..=5 {
+                    let exactly = (start..=end).filter(|&n| repeats(n, base, times as usize));
+                    assert_eq!(
+                        repeated_blocks(start, end, base, Repetitions::Exactly(times)),
+                        tally(exactly),
+                        "base {} {}..={} exactly {}",
+                        base,
+                        start,
+                        end,
+                        times
+                    );
+                    let at_least = (start..=end)
+                        .filter(|&n| (times as usize..=64).any(|t| repeats(n, base, t)));
+                    assert_eq!(
+                        repeated_blocks(start, end, base, Repetitions::AtLeast(times)),
+                        tally(at_least),
+                        "base {} {}..={} at least {}",
+                        base,
+                        start,
+                        end,
+                        times
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn repeated_blocks_near_the_top_of_u64() {
+        let end = u64::MAX;
+        let start = end - 100_000;
+        let brute = tally((start..=end).filter(|&n| repeats(n, 10, 2)));
+        assert_eq!(
+            repeated_blocks(start, end, 10, Repetitions::Exactly(2)),
+            brute
+        );
+        let all = repeated_blocks(1 << 63, end, 2, Repetitions::Exactly(64));
+        assert_eq!(
+            all,
+            Tally {
+                count: 1,
+                sum: u128::from(end)
+            }
+        );
+    }
+}
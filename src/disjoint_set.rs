@@ -0,0 +1,215 @@
+use std::cmp::Reverse;
+
+/// What a [`DisjointSet::union`] did.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Merge {
+    /// Both elements were already in the same component.
+    Same,
+    /// Two components were joined into one.
+    Joined,
+    /// Two components were joined, leaving a single component with every
+    /// element in it. Never reported for fewer than two elements, which are
+    /// never apart to begin with.
+    Unified,
+}
+
+/// Union-find over the elements `0..len`, with path compression and union by
+/// size.
+#[derive(Debug, Clone)]
+pub struct DisjointSet {
+    parent: Vec<usize>,
+    size: Vec<usize>,
+    components: usize,
+}
+
+impl DisjointSet {
+    /// `len` elements, each in a component of its own.
+    pub fn new(len: usize) -> Self {
+        DisjointSet {
+            parent: (0..len).collect(),
+            size: vec![1; len],
+            components: len,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.parent.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.parent.is_empty()
+    }
+
+    /// Number of components.
+    pub fn components(&self) -> usize {
+        self.components
+    }
+
+    /// The representative of the component containing `element`.
+    pub fn find(&mut self, element: usize) -> usize {
+        let mut root = element;
+        while self.parent[root] != root {
+            root = self.parent[root];
+        }
+        let mut current = element;
+        while self.parent[current] != root {
+            current = std::mem::replace(&mut self.parent[current], root);
+        }
+        root
+    }
+
+    pub fn same(&mut self, a: usize, b: usize) -> bool {
+        self.find(a) == self.find(b)
+    }
+
+    /// Join the components containing `a` and `b`.
+    pub fn union(&mut self, a: usize, b: usize) -> Merge {
+        let (mut a, mut b) = (self.find(a), self.find(b));
+        if a == b {
+            return Merge::Same;
+        }
+        if self.size[a] < self.size[b] {
+            std::mem::swap(&mut a, &mut b);
+        }
+        self.parent[b] = a;
+        self.size[a] += self.size[b];
+        self.components -= 1;
+        if self.components == 1 {
+            Merge::Unified
+        } else {
+            Merge::Joined
+        }
+    }
+
+    /// Join each pair in turn, stopping at the one that leaves a single
+    /// component, which is returned. `None` if the pairs run out first, or
+    /// without touching the pairs if there is no more than one component to
+    /// begin with (as with fewer than two elements), since then no pair is
+    /// the one that unifies them.
+    pub fn unify(
+        &mut self,
+        pairs: impl IntoIterator<Item = (usize, usize)>,
+    ) -> Option<(usize, usize)> {
+        if self.components <= 1 {
+            return None;
+        }
+        pairs
+            .into_iter()
+            .find(|&(a, b)| self.union(a, b) == Merge::Unified)
+    }
+
+    /// Number of elements in the component containing `element`.
+    pub fn component_size(&mut self, element: usize) -> usize {
+        let root = self.find(element);
+        self.size[root]
+    }
+
+    /// Sizes of the `k` largest components, largest first.
+    pub fn largest_components(&self, k: usize) -> Vec<usize> {
+        let mut sizes: Vec<usize> = (0..self.len())
+            .filter(|&element| self.parent[element] == element)
+            .map(|root| self.size[root])
+            .collect();
+        sizes.sort_unstable_by_key(|&size| Reverse(size));
+        sizes.truncate(k);
+        sizes
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn elements_start_apart() {
+        let mut set = DisjointSet::new(4);
+        assert_eq!(set.len(), 4);
+        assert_eq!(set.components(), 4);
+        for element in 0..4 {
+            assert_eq!(set.find(element), element);
+            assert_eq!(set.component_size(element), 1);
+        }
+        assert!(!set.same(0, 1));
+        assert!(DisjointSet::new(0).is_empty());
+    }
+
+    #[test]
+    fn union_reports_what_it_did() {
+        let mut set = DisjointSet::new(4);
+        assert_eq!(set.union(0, 1), Merge::Joined);
+        assert_eq!(set.union(1, 0), Merge::Same);
+        assert_eq!(set.union(2, 3), Merge::Joined);
+        assert_eq!(set.components(), 2);
+        assert!(set.same(0, 1));
+        assert!(!set.same(1, 2));
+        assert_eq!(set.union(3, 0), Merge::Unified);
+        assert_eq!(set.components(), 1);
+        assert_eq!(set.union(0, 2), Merge::Same);
+        let root = set.find(0);
+        assert!((0..4).all(|element| set.find(element) == root));
+    }
+
+    #[test]
+    fn the_larger_component_keeps_its_root() {
+        let mut set = DisjointSet::new(5);
+        set.union(0, 1);
+        set.union(1, 2);
+        let root = set.find(2);
+        set.union(4, 3);
+        set.union(3, 0);
+        assert_eq!(set.find(4), root);
+    }
+
+    #[test]
+    fn find_compresses_long_chains() {
+        let mut set = DisjointSet::new(6);
+        // Joining singletons onto one another by hand builds a chain.
+        for element in 1..6 {
+            set.parent[element] = element - 1;
+        }
+        assert_eq!(set.find(5), 0);
+        assert!(set.parent.iter().all(|&parent| parent == 0));
+    }
+
+    #[test]
+    fn sizes_follow_the_unions() {
+        let mut set = DisjointSet::new(8);
+        for (a, b) in [(0, 1), (1, 2), (3, 4), (5, 5), (2, 0)] {
+            set.union(a, b);
+        }
+        assert_eq!(set.component_size(2), 3);
+        assert_eq!(set.component_size(4), 2);
+        assert_eq!(set.component_size(7), 1);
+        assert_eq!(set.components(), 5);
+        assert_eq!(set.largest_components(2), [3, 2]);
+        assert_eq!(set.largest_components(10), [3, 2, 1, 1, 1]);
+        assert!(set.largest_components(0).is_empty());
+    }
+
+    #[test]
+    fn unify_stops_at_the_pair_that_joins_everything() {
+        let mut set = DisjointSet::new(4);
+        let pairs = [(0, 1), (2, 3), (1, 0), (1, 3), (0, 2)];
+        let mut pairs = pairs.into_iter();
+        assert_eq!(set.unify(pairs.by_ref()), Some((1, 3)));
+        assert_eq!(pairs.next(), Some((0, 2)));
+        assert_eq!(set.components(), 1);
+
+        let mut set = DisjointSet::new(4);
+        assert_eq!(set.unify([(0, 1), (2, 3)]), None);
+        assert_eq!(set.components(), 2);
+    }
+
+    #[test]
+    fn nothing_unifies_what_is_never_apart() {
+        for len in [0, 1] {
+            let mut set = DisjointSet::new(len);
+            let mut pairs = [(0, 0)].into_iter();
+            assert_eq!(set.unify(pairs.by_ref()), None);
+            assert_eq!(pairs.next(), Some((0, 0)));
+        }
+        let mut set = DisjointSet::new(2);
+        set.union(0, 1);
+        assert_eq!(set.unify([(1, 0)]), None);
+    }
+}
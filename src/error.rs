@@ -0,0 +1,101 @@
+use std::fmt::{Debug, Display, Formatter};
+use std::io;
+
+/// Errors shared by the library and the individual days.
+pub enum Error {
+    /// The program was invoked with missing or invalid arguments.
+    Usage(String),
+    /// An input file could not be opened or read.
+    Io { path: String, source: io::Error },
+    /// Required configuration, such as the session token, is missing.
+    Config(String),
+    /// A request could not reach the server at all.
+    Offline { url: String, reason: String },
+    /// The server answered a request with an unexpected status.
+    Http { url: String, status: u16 },
+    /// An answer was not submitted because earlier submissions rule it out.
+    Refused(String),
+    /// A line of input did not match the format a parser expected.
+    Parse {
+        file: Option<String>,
+        line: usize,
+        expected: String,
+        found: String,
+    },
+}
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+impl Error {
+    /// Build a parse error for the 1-based `line` of an input.
+    pub fn parse(line: usize, expected: impl Into<String>, found: impl Into<String>) -> Self {
+        Error::Parse {
+            file: None,
+            line,
+            expected: expected.into(),
+            found: found.into(),
+        }
+    }
+
+    /// Attach the name of the file being parsed to a parse error.
+    pub fn in_file(self, path: &str) -> Self {
+        match self {
+            Error::Parse {
+                file: None,
+                line,
+                expected,
+                found,
+            } => Error::Parse {
+                file: Some(path.to_string()),
+                line,
+                expected,
+                found,
+            },
+            other => other,
+        }
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Usage(usage) => write!(f, "usage: {}", usage),
+            Error::Io { path, source } => write!(f, "{}: {}", path, source),
+            Error::Config(message) => write!(f, "configuration: {}", message),
+            Error::Offline { url, reason } => {
+                write!(f, "{}: could not reach server, are you offline? ({})", url, reason)
+            }
+            Error::Http { url, status } => write!(f, "{}: server returned HTTP {}", url, status),
+            Error::Refused(reason) => write!(f, "not submitting: {}", reason),
+            Error::Parse {
+                file,
+                line,
+                expected,
+                found,
+            } => {
+                match file {
+                    Some(file) => write!(f, "{}:{}: ", file, line)?,
+                    None => write!(f, "line {}: ", line)?,
+                }
+                write!(f, "expected {}, found `{}`", expected, found)
+            }
+        }
+    }
+}
+
+// `main` prints the `Debug` form of the error it returns, so make that the
+// readable message rather than the derived struct dump.
+impl Debug for Error {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        Display::fmt(self, f)
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Io { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,424 @@
+/// An exact cover problem, solved with Knuth's Dancing Links: choose rows so
+/// that every primary column is covered exactly once and every secondary
+/// column at most once.
+///
+/// The links live in flat arrays indexed by node, node 0 being the root,
+/// the next nodes the column headers and the rest the entries of each row.
+#[derive(Debug, Clone)]
+pub struct ExactCover {
+    left: Vec<usize>,
+    right: Vec<usize>,
+    up: Vec<usize>,
+    down: Vec<usize>,
+    /// The column header of each node.
+    column: Vec<usize>,
+    /// The row of each entry, `usize::MAX` for the root and headers.
+    row: Vec<usize>,
+    /// The number of rows left in each column, by header node.
+    size: Vec<usize>,
+    /// Whether each column is still to be covered, by header node.
+    open: Vec<bool>,
+    primary: usize,
+    rows: usize,
+}
+
+impl ExactCover {
+    /// Columns `0..primary` must be covered, columns `primary..primary +
+    /// secondary` may be.
+    pub fn new(primary: usize, secondary: usize) -> Self {
+        let headers = primary + secondary;
+        let mut cover = ExactCover {
+            left: Vec::new(),
+            right: Vec::new(),
+            up: Vec::new(),
+            down: Vec::new(),
+            column: Vec::new(),
+            row: Vec::new(),
+            size: vec![0; headers + 1],
+            open: vec![true; headers + 1],
+            primary,
+            rows: 0,
+        };
+        for node in 0..=headers {
+            // Only the primary columns are linked into the root's list, so
+            // the search never has to pick a secondary one.
+            let (left, right) = if node <= primary {
+                ((node + primary) % (primary + 1), (node + 1) % (primary + 1))
+            } else {
+                (node, node)
+            };
+            cover.left.push(left);
+            cover.right.push(right);
+            cover.up.push(node);
+            cover.down.push(node);
+            cover.column.push(node);
+            cover.row.push(usize::MAX);
+        }
+        cover
+    }
+
+    /// Add a row covering `columns`, returning its index.
+    pub fn add_row(&mut self, columns: &[usize]) -> usize {
+        assert!(!columns.is_empty(), "a row needs to cover a column");
+        let row = self.rows;
+        self.rows += 1;
+        let first = self.left.len();
+        for (idx, &column) in columns.iter().enumerate() {
+            let header = column + 1;
+            assert!(header < self.size.len(), "column {column} out of range");
+            let node = self.left.len();
+            let last = first + columns.len() - 1;
+            self.left.push(if idx == 0 { last } else { node - 1 });
+            self.right.push(if node == last { first } else { node + 1 });
+            self.up.push(self.up[header]);
+            self.down.push(header);
+            let above = self.up[header];
+            self.down[above] = node;
+            self.up[header] = node;
+            self.column.push(header);
+            self.row.push(row);
+            self.size[header] += 1;
+        }
+        row
+    }
+
+    fn cover(&mut self, header: usize) {
+        let (left, right) = (self.left[header], self.right[header]);
+        self.right[left] = right;
+        self.left[right] = left;
+        let mut row = self.down[header];
+        while row != header {
+            let mut node = self.right[row];
+            while node != row {
+                let (up, down) = (self.up[node], self.down[node]);
+                self.down[up] = down;
+                self.up[down] = up;
+                self.size[self.column[node]] -= 1;
+                node = self.right[node];
+            }
+            row = self.down[row];
+        }
+    }
+
+    fn uncover(&mut self, header: usize) {
+        let mut row = self.up[header];
+        while row != header {
+            let mut node = self.left[row];
+            while node != row {
+                let (up, down) = (self.up[node], self.down[node]);
+                self.down[up] = node;
+                self.up[down] = node;
+                self.size[self.column[node]] += 1;
+                node = self.left[node];
+            }
+            row = self.up[row];
+        }
+        let (left, right) = (self.left[header], self.right[header]);
+        self.right[left] = header;
+        self.left[right] = header;
+    }
+
+    /// The first solution found, as the rows chosen, trying only the rows
+    /// `allow` accepts given those chosen so far. Columns are picked with the
+    /// fewest rows left first, the earliest of those that tie.
+    pub fn solve(&mut self, allow: impl FnMut(&[usize], usize) -> bool) -> Option<Vec<usize>> {
+        self.solve_leaving(usize::MAX, allow)
+    }
+
+    /// Like [`ExactCover::solve`], but leaving no more than `slack` secondary
+    /// columns uncovered. The search backs off as soon as more of them than
+    /// that have no rows left, and a secondary column with fewer rows left
+    /// than any primary one is picked first, trying each row and then
+    /// leaving it uncovered.
+    pub fn solve_leaving(
+        &mut self,
+        slack: usize,
+        mut allow: impl FnMut(&[usize], usize) -> bool,
+    ) -> Option<Vec<usize>> {
+        let mut chosen = Vec::new();
+        self.search(&mut chosen, slack, &mut allow)
+            .then_some(chosen)
+    }
+
+    fn search(
+        &mut self,
+        chosen: &mut Vec<usize>,
+        slack: usize,
+        allow: &mut impl FnMut(&[usize], usize) -> bool,
+    ) -> bool {
+        // The open secondary columns, how many of them no row can cover any
+        // more, and the one with the fewest rows left of the others. Without a
+        // limit on the slack none of that matters.
+        let (mut open, mut dead, mut fewest) = (0, 0, None);
+        if slack < usize::MAX {
+            for header in self.primary + 1..self.size.len() {
+                if !self.open[header] {
+                    continue;
+                }
+                open += 1;
+                if self.size[header] == 0 {
+                    dead += 1;
+                } else if fewest.is_none_or(|fewest| self.size[header] < self.size[fewest]) {
+                    fewest = Some(header);
+                }
+            }
+            if dead > slack {
+                return false;
+            }
+        }
+        if self.right[0] == 0 && open <= slack {
+            return true;
+        }
+        let mut header = self.right[0];
+        let mut best = header;
+        while header != 0 {
+            if self.size[header] < self.size[best] {
+                best = header;
+            }
+            header = self.right[header];
+        }
+        if best != 0 && self.size[best] == 0 {
+            return false;
+        }
+
+        match fewest {
+            Some(secondary) if best == 0 || self.size[secondary] + 1 < self.size[best] => {
+                self.cover(secondary);
+                self.open[secondary] = false;
+                let found = self.try_rows(secondary, chosen, slack, allow)
+                    || (slack > 0 && self.search(chosen, slack - 1, allow));
+                self.open[secondary] = true;
+                self.uncover(secondary);
+                found
+            }
+            _ if best == 0 => false,
+            _ => {
+                self.cover(best);
+                let found = self.try_rows(best, chosen, slack, allow);
+                self.uncover(best);
+                found
+            }
+        }
+    }
+
+    /// Try each row left in the column at `header`, which has been covered,
+    /// as the one that covers it.
+    fn try_rows(
+        &mut self,
+        header: usize,
+        chosen: &mut Vec<usize>,
+        slack: usize,
+        allow: &mut impl FnMut(&[usize], usize) -> bool,
+    ) -> bool {
+        let mut row = self.down[header];
+        while row != header {
+            if allow(chosen, self.row[row]) {
+                chosen.push(self.row[row]);
+                let mut node = self.right[row];
+                while node != row {
+                    self.cover(self.column[node]);
+                    self.open[self.column[node]] = false;
+                    node = self.right[node];
+                }
+                let found = self.search(chosen, slack, allow);
+                let mut node = self.left[row];
+                while node != row {
+                    self.open[self.column[node]] = true;
+                    self.uncover(self.column[node]);
+                    node = self.left[node];
+                }
+                if found {
+                    return true;
+                }
+                chosen.pop();
+            }
+            row = self.down[row];
+        }
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Whether `chosen` covers each primary column once and each secondary
+    /// column at most once.
+    fn is_cover(rows: &[Vec<usize>], primary: usize, columns: usize, chosen: &[usize]) -> bool {
+        let mut covered = vec![0; columns];
+        for &row in chosen {
+            for &column in &rows[row] {
+                covered[column] += 1;
+            }
+        }
+        covered
+            .iter()
+            .enumerate()
+            .all(|(column, &times)| times == 1 || (column >= primary && times == 0))
+    }
+
+    fn problem(primary: usize, secondary: usize, rows: &[Vec<usize>]) -> ExactCover {
+        let mut cover = ExactCover::new(primary, secondary);
+        for (idx, row) in rows.iter().enumerate() {
+            assert_eq!(cover.add_row(row), idx);
+        }
+        cover
+    }
+
+    #[test]
+    fn knuths_example() {
+        let rows = [
+            vec![2, 4, 5],
+            vec![0, 3, 6],
+            vec![1, 2, 5],
+            vec![0, 3],
+            vec![1, 6],
+            vec![3, 4, 6],
+        ];
+        let mut chosen = problem(7, 0, &rows).solve(|_, _| true).unwrap();
+        chosen.sort();
+        assert_eq!(chosen, [0, 3, 4]);
+    }
+
+    #[test]
+    fn nothing_to_cover_needs_no_rows() {
+        assert_eq!(ExactCover::new(0, 0).solve(|_, _| true), Some(Vec::new()));
+        let mut optional = problem(0, 2, &[vec![0], vec![1]]);
+        assert_eq!(optional.solve(|_, _| true), Some(Vec::new()));
+    }
+
+    #[test]
+    fn unsatisfiable_problems() {
+        assert_eq!(ExactCover::new(1, 0).solve(|_, _| true), None);
+        assert_eq!(
+            problem(3, 0, &[vec![0, 1], vec![1, 2]]).solve(|_, _| true),
+            None
+        );
+        assert_eq!(
+            problem(2, 1, &[vec![0, 2], vec![1, 2]]).solve(|_, _| true),
+            None
+        );
+    }
+
+    #[test]
+    fn secondary_columns_may_be_left_uncovered() {
+        let rows = [vec![0, 2], vec![1, 2], vec![1]];
+        let chosen = problem(2, 1, &rows).solve(|_, _| true).unwrap();
+        assert!(is_cover(&rows, 2, 3, &chosen));
+        let mut chosen = chosen;
+        chosen.sort();
+        assert_eq!(chosen, [0, 2]);
+    }
+
+    /// Number of secondary columns `chosen` leaves uncovered.
+    fn uncovered(rows: &[Vec<usize>], primary: usize, columns: usize, chosen: &[usize]) -> usize {
+        (primary..columns)
+            .filter(|column| chosen.iter().all(|&row| !rows[row].contains(column)))
+            .count()
+    }
+
+    #[test]
+    fn slack_limits_the_secondary_columns_left_uncovered() {
+        let rows = [vec![0], vec![0, 1], vec![0, 1, 2]];
+        for (slack, fewest) in [(0, 2), (1, 1), (2, 0)] {
+            let chosen = problem(1, 2, &rows)
+                .solve_leaving(slack, |_, _| true)
+                .unwrap();
+            assert!(is_cover(&rows, 1, 3, &chosen));
+            assert!(uncovered(&rows, 1, 3, &chosen) <= slack);
+            assert!(
+                chosen[0] >= fewest,
+                "{} with a slack of {}",
+                chosen[0],
+                slack
+            );
+        }
+        // Rows of secondary columns alone are chosen to keep within it.
+        let rows = [vec![0], vec![1], vec![2]];
+        let mut chosen = problem(1, 2, &rows).solve_leaving(0, |_, _| true).unwrap();
+        chosen.sort();
+        assert_eq!(chosen, [0, 1, 2]);
+    }
+
+    #[test]
+    fn secondary_columns_without_rows_use_up_the_slack() {
+        let rows = [vec![0], vec![0, 1]];
+        let mut cover = problem(1, 2, &rows);
+        assert_eq!(cover.solve_leaving(0, |_, _| true), None);
+        assert_eq!(cover.solve_leaving(1, |_, _| true), Some(vec![1]));
+        assert_eq!(cover.solve_leaving(1, |_, row| row == 0), None);
+        assert_eq!(cover.solve_leaving(2, |_, row| row == 0), Some(vec![0]));
+    }
+
+    #[test]
+    fn allow_rules_rows_out() {
+        let rows = [vec![0, 1], vec![0], vec![1]];
+        let mut cover = problem(2, 0, &rows);
+        let mut chosen = cover.solve(|_, row| row != 0).unwrap();
+        chosen.sort();
+        assert_eq!(chosen, [1, 2]);
+        assert_eq!(cover.solve(|chosen, _| chosen.is_empty()), Some(vec![0]));
+        assert_eq!(cover.solve(|_, row| row == 1), None);
+        // The search leaves the links as it found them.
+        assert!(cover.solve(|_, _| true).is_some());
+    }
+
+    #[test]
+    fn solutions_match_trying_every_set_of_rows() {
+        let mut state: u64 = 0x2545_f491_4f6c_dd1d;
+        let mut next = move |n: u64| {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            (state % n) as usize
+        };
+        for _ in 0..200 {
+            let (primary, secondary) = (1 + next(5), next(3));
+            let columns = primary + secondary;
+            let rows: Vec<Vec<usize>> = (0..next(9))
+                .map(|_| {
+                    let mut row: Vec<usize> = (0..columns).filter(|_| next(3) == 0).collect();
+                    if row.is_empty() {
+                        row.push(next(columns as u64));
+                    }
+                    row
+                })
+                .collect();
+            let exists = (0..1usize << rows.len()).any(|set| {
+                let chosen: Vec<usize> = (0..rows.len()).filter(|&i| set & (1 << i) != 0).collect();
+                is_cover(&rows, primary, columns, &chosen)
+            });
+            match problem(primary, secondary, &rows).solve(|_, _| true) {
+                Some(chosen) => assert!(is_cover(&rows, primary, columns, &chosen)),
+                None => assert!(!exists, "missed a cover of {:?}", rows),
+            }
+            for slack in 0..=secondary {
+                let exists = (0..1usize << rows.len()).any(|set| {
+                    let chosen: Vec<usize> =
+                        (0..rows.len()).filter(|&i| set & (1 << i) != 0).collect();
+                    is_cover(&rows, primary, columns, &chosen)
+                        && uncovered(&rows, primary, columns, &chosen) <= slack
+                });
+                match problem(primary, secondary, &rows).solve_leaving(slack, |_, _| true) {
+                    Some(chosen) => {
+                        assert!(is_cover(&rows, primary, columns, &chosen));
+                        assert!(uncovered(&rows, primary, columns, &chosen) <= slack);
+                    }
+                    None => assert!(!exists, "missed a cover of {:?} within {}", rows, slack),
+                }
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "a row needs to cover a column")]
+    fn rows_must_not_be_empty() {
+        ExactCover::new(1, 0).add_row(&[]);
+    }
+
+    #[test]
+    #[should_panic(expected = "column 2 out of range")]
+    fn rows_must_stay_within_the_columns() {
+        ExactCover::new(1, 1).add_row(&[2]);
+    }
+}
@@ -0,0 +1,425 @@
+use std::fmt::{Display, Formatter};
+
+/// A fixed length vector over GF(2), packed into words.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct BitVector {
+    words: Vec<u64>,
+    len: usize,
+}
+
+impl BitVector {
+    /// `len` zeros.
+    pub fn zeros(len: usize) -> Self {
+        BitVector {
+            words: vec![0; len.div_ceil(64)],
+            len,
+        }
+    }
+
+    /// A vector of length `len` with ones at `indices`.
+    pub fn from_indices(len: usize, indices: impl IntoIterator<Item = usize>) -> Self {
+        let mut vector = BitVector::zeros(len);
+        for index in indices {
+            vector.set(index, true);
+        }
+        vector
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn get(&self, index: usize) -> bool {
+        assert!(
+            index < self.len,
+            "bit {index} out of range for {}",
+            self.len
+        );
+        self.words[index / 64] >> (index % 64) & 1 == 1
+    }
+
+    pub fn set(&mut self, index: usize, value: bool) {
+        assert!(
+            index < self.len,
+            "bit {index} out of range for {}",
+            self.len
+        );
+        let mask = 1 << (index % 64);
+        if value {
+            self.words[index / 64] |= mask;
+        } else {
+            self.words[index / 64] &= !mask;
+        }
+    }
+
+    pub fn flip(&mut self, index: usize) {
+        self.set(index, !self.get(index));
+    }
+
+    /// Add `other` to this vector, which over GF(2) is an exclusive or.
+    pub fn add(&mut self, other: &BitVector) {
+        assert_eq!(self.len, other.len, "vectors of different lengths");
+        for (word, other) in self.words.iter_mut().zip(&other.words) {
+            *word ^= other;
+        }
+    }
+
+    /// Number of ones, the Hamming weight.
+    pub fn count_ones(&self) -> usize {
+        self.words
+            .iter()
+            .map(|word| word.count_ones() as usize)
+            .sum()
+    }
+
+    /// Indices of the ones, in increasing order.
+    pub fn ones(&self) -> impl Iterator<Item = usize> + '_ {
+        (0..self.len).filter(|&index| self.get(index))
+    }
+}
+
+impl Display for BitVector {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for index in 0..self.len {
+            write!(f, "{}", u8::from(self.get(index)))?;
+        }
+        Ok(())
+    }
+}
+
+/// The equations of a [`LinearSystem`] contradict each other.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Unsolvable;
+
+impl Display for Unsolvable {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "the equations have no solution")
+    }
+}
+
+/// The most free variables [`SolutionSpace::minimum_weight`] will search,
+/// for about 16 million solutions.
+pub const MAX_SEARCH_DIMENSION: usize = 24;
+
+/// A [`SolutionSpace`] has too many solutions to search them all.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TooManySolutions {
+    pub dimension: usize,
+}
+
+impl Display for TooManySolutions {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "the 2^{} solutions are too many to search",
+            self.dimension
+        )
+    }
+}
+
+/// Linear equations `a · x = b` over GF(2) in a fixed number of variables.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LinearSystem {
+    variables: usize,
+    equations: Vec<(BitVector, bool)>,
+}
+
+impl LinearSystem {
+    pub fn new(variables: usize) -> Self {
+        LinearSystem {
+            variables,
+            equations: Vec::new(),
+        }
+    }
+
+    pub fn variables(&self) -> usize {
+        self.variables
+    }
+
+    /// Add the equation that the variables with a one in `coefficients` sum
+    /// to `value`.
+    pub fn equation(&mut self, coefficients: BitVector, value: bool) {
+        assert_eq!(
+            coefficients.len(),
+            self.variables,
+            "an equation needs a coefficient for every variable"
+        );
+        self.equations.push((coefficients, value));
+    }
+
+    /// Every solution, found by Gauss-Jordan elimination.
+    pub fn solve(&self) -> Result<SolutionSpace, Unsolvable> {
+        let mut rows = self.equations.clone();
+        let mut pivots = Vec::new();
+        for column in 0..self.variables {
+            let rank = pivots.len();
+            let Some(found) = (rank..rows.len()).find(|&row| rows[row].0.get(column)) else {
+                continue;
+            };
+            rows.swap(rank, found);
+            let (pivot, value) = rows[rank].clone();
+            for (row, (coefficients, row_value)) in rows.iter_mut().enumerate() {
+                if row != rank && coefficients.get(column) {
+                    coefficients.add(&pivot);
+                    *row_value ^= value;
+                }
+            }
+            pivots.push(column);
+        }
+        // Whatever is left below the pivots reads `0 = value`.
+        if rows[pivots.len()..].iter().any(|&(_, value)| value) {
+            return Err(Unsolvable);
+        }
+
+        let mut particular = BitVector::zeros(self.variables);
+        for (row, &pivot) in pivots.iter().enumerate() {
+            particular.set(pivot, rows[row].1);
+        }
+        let null_space = (0..self.variables)
+            .filter(|column| !pivots.contains(column))
+            .map(|free| {
+                let mut vector = BitVector::zeros(self.variables);
+                vector.set(free, true);
+                for (row, &pivot) in pivots.iter().enumerate() {
+                    vector.set(pivot, rows[row].0.get(free));
+                }
+                vector
+            })
+            .collect();
+        Ok(SolutionSpace {
+            particular,
+            null_space,
+        })
+    }
+}
+
+/// The solutions of a [`LinearSystem`]: one particular solution plus any sum
+/// of vectors from a basis of the null space.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SolutionSpace {
+    particular: BitVector,
+    null_space: Vec<BitVector>,
+}
+
+impl SolutionSpace {
+    /// The solution with every free variable zero.
+    pub fn particular(&self) -> &BitVector {
+        &self.particular
+    }
+
+    /// A basis of the null space, one vector per free variable.
+    pub fn null_space(&self) -> &[BitVector] {
+        &self.null_space
+    }
+
+    /// Number of free variables, so there are `2^dimension` solutions.
+    pub fn dimension(&self) -> usize {
+        self.null_space.len()
+    }
+
+    /// Every solution, each differing from the one before in a single basis
+    /// vector, in Gray code order.
+    pub fn iter(&self) -> Solutions<'_> {
+        Solutions {
+            space: self,
+            current: self.particular.clone(),
+            step: 0,
+            total: 1u128.checked_shl(self.dimension() as u32),
+        }
+    }
+
+    /// The solution with the fewest ones, the first in [`SolutionSpace::iter`]
+    /// order on a tie.
+    ///
+    /// Finding it is NP-hard in general, so this tries all `2^dimension`
+    /// solutions, and refuses to above [`MAX_SEARCH_DIMENSION`] free
+    /// variables rather than run for hours.
+    pub fn minimum_weight(&self) -> Result<BitVector, TooManySolutions> {
+        if self.dimension() > MAX_SEARCH_DIMENSION {
+            return Err(TooManySolutions {
+                dimension: self.dimension(),
+            });
+        }
+        Ok(self
+            .iter()
+            .min_by_key(BitVector::count_ones)
+            .expect("there is always a particular solution"))
+    }
+}
+
+/// Enumeration of a [`SolutionSpace`], see [`SolutionSpace::iter`].
+#[derive(Debug, Clone)]
+pub struct Solutions<'a> {
+    space: &'a SolutionSpace,
+    current: BitVector,
+    step: u128,
+    /// `None` when there are more solutions than a `u128` can count.
+    total: Option<u128>,
+}
+
+impl Iterator for Solutions<'_> {
+    type Item = BitVector;
+
+    fn next(&mut self) -> Option<BitVector> {
+        if self.total.is_some_and(|total| self.step >= total) {
+            return None;
+        }
+        if self.step > 0 {
+            let changed = self.step.trailing_zeros() as usize;
+            self.current.add(&self.space.null_space[changed]);
+        }
+        self.step += 1;
+        Some(self.current.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The system with an equation for each `(variables, value)`.
+    fn system(variables: usize, equations: &[(&[usize], bool)]) -> LinearSystem {
+        let mut system = LinearSystem::new(variables);
+        for &(ones, value) in equations {
+            system.equation(
+                BitVector::from_indices(variables, ones.iter().copied()),
+                value,
+            );
+        }
+        system
+    }
+
+    fn satisfies(system: &LinearSystem, x: &BitVector) -> bool {
+        system.equations.iter().all(|(coefficients, value)| {
+            coefficients.ones().filter(|&i| x.get(i)).count() % 2 == usize::from(*value)
+        })
+    }
+
+    /// Every assignment of `variables` variables, as bits of a counter.
+    fn assignments(variables: usize) -> impl Iterator<Item = BitVector> {
+        (0u32..1 << variables).map(move |bits| {
+            BitVector::from_indices(variables, (0..variables).filter(|i| bits & (1 << i) != 0))
+        })
+    }
+
+    #[test]
+    fn bit_vectors_span_several_words() {
+        let mut vector = BitVector::from_indices(130, [0, 63, 64, 129]);
+        assert_eq!(vector.count_ones(), 4);
+        vector.flip(64);
+        vector.flip(65);
+        assert_eq!(vector.ones().collect::<Vec<_>>(), [0, 63, 65, 129]);
+        vector.add(&BitVector::from_indices(130, [0, 1]));
+        assert_eq!(vector.ones().collect::<Vec<_>>(), [1, 63, 65, 129]);
+        assert_eq!(BitVector::from_indices(4, [1, 2]).to_string(), "0110");
+    }
+
+    #[test]
+    fn empty_bit_vector() {
+        let empty = BitVector::zeros(0);
+        assert!(empty.is_empty());
+        assert_eq!(empty.count_ones(), 0);
+        assert_eq!(empty.to_string(), "");
+    }
+
+    #[test]
+    #[should_panic(expected = "out of range")]
+    fn bits_past_the_end_are_refused() {
+        BitVector::zeros(64).get(64);
+    }
+
+    #[test]
+    fn unique_solution() {
+        // x0 + x1 = 1, x1 + x2 = 0, x0 + x1 + x2 = 0
+        let system = system(3, &[(&[0, 1], true), (&[1, 2], false), (&[0, 1, 2], false)]);
+        let space = system.solve().unwrap();
+        assert_eq!(space.dimension(), 0);
+        assert_eq!(space.particular().to_string(), "011");
+        assert_eq!(
+            space.iter().collect::<Vec<_>>(),
+            [space.particular().clone()]
+        );
+    }
+
+    #[test]
+    fn inconsistent_system() {
+        let system = system(3, &[(&[0, 1], true), (&[1, 2], true), (&[0, 2], true)]);
+        assert_eq!(system.solve(), Err(Unsolvable));
+        // No variables at all, but asked for an odd sum.
+        assert_eq!(self::system(2, &[(&[], true)]).solve(), Err(Unsolvable));
+    }
+
+    #[test]
+    fn no_equations_leave_every_variable_free() {
+        let space = LinearSystem::new(3).solve().unwrap();
+        assert_eq!(space.dimension(), 3);
+        assert_eq!(space.iter().count(), 8);
+        assert_eq!(space.minimum_weight(), Ok(BitVector::zeros(3)));
+
+        let nothing = LinearSystem::new(0).solve().unwrap();
+        assert_eq!(nothing.iter().collect::<Vec<_>>(), [BitVector::zeros(0)]);
+    }
+
+    #[test]
+    fn solutions_match_trying_every_assignment() {
+        let mut state: u32 = 7;
+        let mut next = move |bound: u32| {
+            state = state.wrapping_mul(1_664_525).wrapping_add(1_013_904_223);
+            (state >> 8) % bound
+        };
+        for _ in 0..60 {
+            let variables = 1 + next(8) as usize;
+            let mut system = LinearSystem::new(variables);
+            for _ in 0..next(7) {
+                let ones = (0..variables).filter(|_| next(2) == 1);
+                system.equation(BitVector::from_indices(variables, ones), next(2) == 1);
+            }
+
+            let expected: Vec<BitVector> = assignments(variables)
+                .filter(|x| satisfies(&system, x))
+                .collect();
+            match system.solve() {
+                Err(Unsolvable) => assert!(expected.is_empty()),
+                Ok(space) => {
+                    let mut found: Vec<BitVector> = space.iter().collect();
+                    assert_eq!(found.len(), 1 << space.dimension());
+                    found.sort_by_key(ToString::to_string);
+                    let mut expected = expected.clone();
+                    expected.sort_by_key(ToString::to_string);
+                    assert_eq!(found, expected);
+
+                    let minimum = space.minimum_weight().unwrap();
+                    let fewest = expected.iter().map(BitVector::count_ones).min();
+                    assert_eq!(Some(minimum.count_ones()), fewest);
+                    assert!(satisfies(&system, &minimum));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn solution_order_changes_one_basis_vector_at_a_time() {
+        let space = LinearSystem::new(4).solve().unwrap();
+        let solutions: Vec<BitVector> = space.iter().collect();
+        for pair in solutions.windows(2) {
+            let mut difference = pair[0].clone();
+            difference.add(&pair[1]);
+            assert_eq!(difference.count_ones(), 1);
+        }
+    }
+
+    #[test]
+    fn minimum_weight_refuses_huge_spaces() {
+        let space = LinearSystem::new(MAX_SEARCH_DIMENSION + 1).solve().unwrap();
+        assert_eq!(
+            space.minimum_weight(),
+            Err(TooManySolutions {
+                dimension: MAX_SEARCH_DIMENSION + 1
+            })
+        );
+    }
+}
@@ -0,0 +1,573 @@
+use crate::error::{Error, Result};
+use std::collections::{HashMap, VecDeque};
+use std::fmt::{Display, Formatter};
+
+mod cliques;
+mod components;
+mod dot;
+mod flow;
+mod shortest;
+
+pub use components::Dominators;
+pub use dot::DotOptions;
+pub use flow::Flow;
+pub use shortest::ShortestPaths;
+
+/// A node of a [`Graph`], numbered in the order its name was first seen.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct NodeId(usize);
+
+impl NodeId {
+    pub fn index(self) -> usize {
+        self.0
+    }
+}
+
+/// A [`Graph`] has a cycle where it needs to be acyclic, through `nodes` in
+/// order, each with an edge to the next and the last back to the first.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cycle {
+    pub nodes: Vec<NodeId>,
+}
+
+impl Display for Cycle {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "the graph has a cycle through {} nodes",
+            self.nodes.len()
+        )
+    }
+}
+
+/// A directed graph whose nodes are named by strings, interned to
+/// [`NodeId`]s so that the algorithms work on indices.
+#[derive(Debug, Clone, Default)]
+pub struct Graph {
+    names: Vec<String>,
+    ids: HashMap<String, NodeId>,
+    successors: Vec<Vec<NodeId>>,
+}
+
+impl Graph {
+    pub fn new() -> Self {
+        Graph::default()
+    }
+
+    /// Parse one node per line, like `aaa: bbb ccc` for a node `aaa` with
+    /// edges to `bbb` and `ccc`. Nodes only named as successors are added
+    /// too, without edges of their own.
+    pub fn parse(input: &str) -> Result<Self> {
+        const EXPECTED: &str = "a node and its successors like `aaa: bbb ccc`";
+        let mut graph = Graph::new();
+        for (idx, line) in input.lines().enumerate() {
+            let (node, successors) = line
+                .split_once(':')
+                .filter(|(node, _)| !node.trim().is_empty())
+                .ok_or_else(|| Error::parse(idx + 1, EXPECTED, line))?;
+            let node = graph.intern(node.trim());
+            for successor in successors.split_whitespace() {
+                let successor = graph.intern(successor);
+                graph.add_edge(node, successor);
+            }
+        }
+        Ok(graph)
+    }
+
+    /// The node named `name`, added without edges if it's new.
+    pub fn intern(&mut self, name: &str) -> NodeId {
+        if let Some(&id) = self.ids.get(name) {
+            return id;
+        }
+        let id = NodeId(self.names.len());
+        self.names.push(name.to_string());
+        self.ids.insert(name.to_string(), id);
+        self.successors.push(Vec::new());
+        id
+    }
+
+    /// The node named `name`, if there is one.
+    pub fn node(&self, name: &str) -> Option<NodeId> {
+        self.ids.get(name).copied()
+    }
+
+    pub fn name(&self, node: NodeId) -> &str {
+        &self.names[node.0]
+    }
+
+    /// Number of nodes.
+    pub fn len(&self) -> usize {
+        self.names.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.names.is_empty()
+    }
+
+    pub fn nodes(&self) -> impl Iterator<Item = NodeId> + '_ {
+        (0..self.len()).map(NodeId)
+    }
+
+    pub fn add_edge(&mut self, from: NodeId, to: NodeId) {
+        self.successors[from.0].push(to);
+    }
+
+    /// The nodes `node` has an edge to, in the order they were added.
+    pub fn successors(&self, node: NodeId) -> &[NodeId] {
+        &self.successors[node.0]
+    }
+
+    /// Every edge as `(from, to)`.
+    pub fn edges(&self) -> impl Iterator<Item = (NodeId, NodeId)> + '_ {
+        self.nodes()
+            .flat_map(move |from| self.successors(from).iter().map(move |&to| (from, to)))
+    }
+
+    /// The same nodes, with every edge turned around.
+    pub fn reversed(&self) -> Graph {
+        let mut reversed = Graph {
+            names: self.names.clone(),
+            ids: self.ids.clone(),
+            successors: vec![Vec::new(); self.len()],
+        };
+        for (from, to) in self.edges() {
+            reversed.add_edge(to, from);
+        }
+        reversed
+    }
+
+    /// Which nodes can be reached from `from`, including `from` itself.
+    fn reach(&self, from: NodeId) -> Vec<bool> {
+        let mut seen = vec![false; self.len()];
+        seen[from.0] = true;
+        let mut stack = vec![from];
+        while let Some(node) = stack.pop() {
+            for &next in self.successors(node) {
+                if !std::mem::replace(&mut seen[next.0], true) {
+                    stack.push(next);
+                }
+            }
+        }
+        seen
+    }
+
+    /// The nodes reachable from `from`, including `from` itself.
+    pub fn reachable_from(&self, from: NodeId) -> Vec<NodeId> {
+        let reach = self.reach(from);
+        self.nodes().filter(|node| reach[node.0]).collect()
+    }
+
+    /// The nodes that can reach `to`, including `to` itself.
+    pub fn reaching(&self, to: NodeId) -> Vec<NodeId> {
+        let reach = self.reversed().reach(to);
+        self.nodes().filter(|node| reach[node.0]).collect()
+    }
+
+    /// Every node, each before all of its successors.
+    pub fn topological_order(&self) -> std::result::Result<Vec<NodeId>, Cycle> {
+        self.order_within(&vec![true; self.len()])
+    }
+
+    /// The nodes marked in `include`, each before its successors among them,
+    /// with Kahn's algorithm.
+    fn order_within(&self, include: &[bool]) -> std::result::Result<Vec<NodeId>, Cycle> {
+        let mut incoming = vec![0; self.len()];
+        for (from, to) in self.edges() {
+            if include[from.0] && include[to.0] {
+                incoming[to.0] += 1;
+            }
+        }
+        let mut ready: VecDeque<NodeId> = self
+            .nodes()
+            .filter(|node| include[node.0] && incoming[node.0] == 0)
+            .collect();
+        let mut order = Vec::new();
+        while let Some(node) = ready.pop_front() {
+            order.push(node);
+            for &next in self.successors(node) {
+                if include[next.0] {
+                    incoming[next.0] -= 1;
+                    if incoming[next.0] == 0 {
+                        ready.push_back(next);
+                    }
+                }
+            }
+        }
+        if order.len() < include.iter().filter(|&&included| included).count() {
+            return Err(self.find_cycle(include, &incoming));
+        }
+        Ok(order)
+    }
+
+    /// A cycle among the nodes Kahn's algorithm left with `incoming` edges.
+    /// Every such node has a predecessor that's left too, so walking back
+    /// through them must come round to a node already seen.
+    fn find_cycle(&self, include: &[bool], incoming: &[usize]) -> Cycle {
+        let left = |node: NodeId| include[node.0] && incoming[node.0] > 0;
+        let mut predecessor = vec![None; self.len()];
+        for (from, to) in self.edges() {
+            if left(from) && left(to) {
+                predecessor[to.0] = Some(from);
+            }
+        }
+        let start = self
+            .nodes()
+            .find(|&node| left(node))
+            .expect("a node is left");
+        let mut visited = vec![false; self.len()];
+        let mut node = start;
+        while !visited[node.0] {
+            visited[node.0] = true;
+            node = predecessor[node.0].expect("every node left has a predecessor left");
+        }
+        let mut nodes = vec![node];
+        let mut current = predecessor[node.0].expect("on the cycle");
+        while current != node {
+            nodes.push(current);
+            current = predecessor[current.0].expect("on the cycle");
+        }
+        nodes.reverse();
+        Cycle { nodes }
+    }
+
+    /// The number of paths from each node marked in `include` to `to`,
+    /// going through marked nodes only, zero for the rest.
+    fn count_within(&self, include: &[bool], to: NodeId) -> std::result::Result<Vec<u128>, Cycle> {
+        let mut counts = vec![0; self.len()];
+        for node in self.order_within(include)?.into_iter().rev() {
+            counts[node.0] = u128::from(node == to)
+                + self
+                    .successors(node)
+                    .iter()
+                    .filter(|next| include[next.0])
+                    .map(|next| counts[next.0])
+                    .sum::<u128>();
+        }
+        Ok(counts)
+    }
+
+    /// The number of paths from every node to `to`, indexed by
+    /// [`NodeId::index`]. Fails if any node that can reach `to` is on a
+    /// cycle, as it would have infinitely many.
+    pub fn paths_to(&self, to: NodeId) -> std::result::Result<Vec<u128>, Cycle> {
+        self.count_within(&self.reversed().reach(to), to)
+    }
+
+    /// The number of paths from `from` to `to`, counted in topological
+    /// order over just the nodes on such a path. Fails if any of them is on a
+    /// cycle, as there would be infinitely many.
+    pub fn count_paths(&self, from: NodeId, to: NodeId) -> std::result::Result<u128, Cycle> {
+        let forward = self.reach(from);
+        let backward = self.reversed().reach(to);
+        let between: Vec<bool> = forward
+            .iter()
+            .zip(&backward)
+            .map(|(&f, &b)| f && b)
+            .collect();
+        if !between[from.0] {
+            return Ok(0);
+        }
+        Ok(self.count_within(&between, to)?[from.0])
+    }
+
+    /// The number of paths from `from` to `to` that visit every one of
+    /// `waypoints`, in any order.
+    ///
+    /// Each such path visits the waypoints in some order and is made up of
+    /// paths between consecutive ones, so this sums, over every order, the
+    /// product of the path counts along it. Orders are built up a waypoint at
+    /// a time, keeping the number of ways to have visited each set of
+    /// waypoints ending at each one, so that only `2^k · k²` segments are
+    /// combined rather than `k!` orders. Fails if a segment that can be
+    /// reached through the earlier waypoints runs through a cycle.
+    pub fn count_paths_via(
+        &self,
+        from: NodeId,
+        to: NodeId,
+        waypoints: &[NodeId],
+    ) -> std::result::Result<u128, Cycle> {
+        let k = waypoints.len();
+        if k == 0 {
+            return self.count_paths(from, to);
+        }
+        let mut segments = HashMap::new();
+        let mut segment = |a: NodeId, b: NodeId| -> std::result::Result<u128, Cycle> {
+            if let Some(&count) = segments.get(&(a, b)) {
+                return Ok(count);
+            }
+            let count = self.count_paths(a, b)?;
+            segments.insert((a, b), count);
+            Ok(count)
+        };
+
+        // ways[visited][last]: paths from `from` through the waypoints in the
+        // set `visited`, ending at waypoint `last`.
+        let mut ways = vec![vec![0u128; k]; 1 << k];
+        for (last, &waypoint) in waypoints.iter().enumerate() {
+            ways[1 << last][last] = segment(from, waypoint)?;
+        }
+        for visited in 1..1usize << k {
+            for last in (0..k).filter(|&last| visited & (1 << last) != 0) {
+                let count = ways[visited][last];
+                if count == 0 {
+                    continue;
+                }
+                for next in (0..k).filter(|&next| visited & (1 << next) == 0) {
+                    let extended = count * segment(waypoints[last], waypoints[next])?;
+                    ways[visited | (1 << next)][next] += extended;
+                }
+            }
+        }
+        let mut total = 0;
+        for (last, &waypoint) in waypoints.iter().enumerate() {
+            let count = ways[(1 << k) - 1][last];
+            if count > 0 {
+                total += count * segment(waypoint, to)?;
+            }
+        }
+        Ok(total)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ids(graph: &Graph, names: &str) -> Vec<NodeId> {
+        names
+            .split_whitespace()
+            .map(|name| graph.node(name).unwrap())
+            .collect()
+    }
+
+    /// Whether `cycle` really runs through edges of `graph`, back to its
+    /// start.
+    fn is_cycle(graph: &Graph, cycle: &Cycle) -> bool {
+        let nodes = &cycle.nodes;
+        !nodes.is_empty()
+            && (0..nodes.len()).all(|i| {
+                let next = nodes[(i + 1) % nodes.len()];
+                graph.successors(nodes[i]).contains(&next)
+            })
+    }
+
+    /// A random graph with edges only from lower to higher numbered nodes,
+    /// so that it's acyclic.
+    fn random_dag(nodes: usize, seed: u64) -> Graph {
+        let mut state = seed;
+        let mut graph = Graph::new();
+        let ids: Vec<NodeId> = (0..nodes).map(|i| graph.intern(&i.to_string())).collect();
+        for a in 0..nodes {
+            for b in a + 1..nodes {
+                state ^= state << 13;
+                state ^= state >> 7;
+                state ^= state << 17;
+                if state.is_multiple_of(3) {
+                    graph.add_edge(ids[a], ids[b]);
+                }
+            }
+        }
+        graph
+    }
+
+    /// Count the paths from `node` to `to` visiting every waypoint by
+    /// walking each of them.
+    fn brute_force_paths(
+        graph: &Graph,
+        node: NodeId,
+        to: NodeId,
+        waypoints: &[NodeId],
+        path: &mut Vec<NodeId>,
+    ) -> u128 {
+        path.push(node);
+        let mut count = 0;
+        if node == to && waypoints.iter().all(|waypoint| path.contains(waypoint)) {
+            count += 1;
+        }
+        for &next in graph.successors(node) {
+            count += brute_force_paths(graph, next, to, waypoints, path);
+        }
+        path.pop();
+        count
+    }
+
+    #[test]
+    fn parse_adds_nodes_only_named_as_successors() {
+        let graph = Graph::parse("aaa: bbb ccc\nbbb: ccc\nddd:\n").unwrap();
+        assert_eq!(graph.len(), 4);
+        let [aaa, bbb, ccc, ddd] = ids(&graph, "aaa bbb ccc ddd")[..] else {
+            unreachable!()
+        };
+        assert_eq!(graph.name(ccc), "ccc");
+        assert_eq!(graph.successors(aaa), [bbb, ccc]);
+        assert!(graph.successors(ccc).is_empty());
+        assert!(graph.successors(ddd).is_empty());
+        assert_eq!(
+            graph.edges().collect::<Vec<_>>(),
+            [(aaa, bbb), (aaa, ccc), (bbb, ccc)]
+        );
+        assert_eq!(graph.node("eee"), None);
+    }
+
+    #[test]
+    fn parse_rejects_lines_without_a_node() {
+        for (input, line) in [("aaa: bbb\nbbb ccc\n", 2), (": bbb\n", 1)] {
+            match Graph::parse(input) {
+                Err(Error::Parse { line: found, .. }) => assert_eq!(found, line),
+                other => panic!("expected a parse error, got {:?}", other),
+            }
+        }
+    }
+
+    #[test]
+    fn empty_graph() {
+        let graph = Graph::parse("").unwrap();
+        assert!(graph.is_empty());
+        assert_eq!(graph.edges().count(), 0);
+        assert_eq!(graph.topological_order(), Ok(Vec::new()));
+        assert!(graph.reversed().is_empty());
+    }
+
+    #[test]
+    fn intern_names_each_node_once() {
+        let mut graph = Graph::new();
+        let a = graph.intern("a");
+        let b = graph.intern("b");
+        assert_eq!(graph.intern("a"), a);
+        assert_eq!((a.index(), b.index()), (0, 1));
+        assert_eq!(graph.len(), 2);
+    }
+
+    #[test]
+    fn reversed_turns_every_edge_around() {
+        let graph = Graph::parse("a: b c\nb: c\n").unwrap();
+        let reversed = graph.reversed();
+        let mut edges: Vec<_> = reversed.edges().map(|(from, to)| (to, from)).collect();
+        edges.sort();
+        assert_eq!(edges, graph.edges().collect::<Vec<_>>());
+        assert_eq!(reversed.node("c"), graph.node("c"));
+    }
+
+    #[test]
+    fn reach_follows_edges_one_way() {
+        let graph = Graph::parse("a: b\nb: c\nd: c\ne: a\n").unwrap();
+        let [a, b, c, d, e] = ids(&graph, "a b c d e")[..] else {
+            unreachable!()
+        };
+        assert_eq!(graph.reachable_from(a), [a, b, c]);
+        assert_eq!(graph.reachable_from(c), [c]);
+        assert_eq!(graph.reaching(c), [a, b, c, d, e]);
+        assert_eq!(graph.reaching(e), [e]);
+    }
+
+    #[test]
+    fn topological_order_puts_every_node_before_its_successors() {
+        for seed in 1..20 {
+            let graph = random_dag(12, seed);
+            let order = graph.topological_order().unwrap();
+            assert_eq!(order.len(), graph.len());
+            let mut position = vec![0; graph.len()];
+            for (i, node) in order.iter().enumerate() {
+                position[node.index()] = i;
+            }
+            assert!(
+                graph
+                    .edges()
+                    .all(|(from, to)| position[from.index()] < position[to.index()])
+            );
+        }
+    }
+
+    #[test]
+    fn topological_order_reports_a_cycle() {
+        let graph = Graph::parse("a: b\nb: c\nc: d b\nd:\n").unwrap();
+        let cycle = graph.topological_order().unwrap_err();
+        assert!(is_cycle(&graph, &cycle));
+        assert_eq!(cycle.nodes.len(), 2);
+        assert_eq!(cycle.to_string(), "the graph has a cycle through 2 nodes");
+
+        let looped = Graph::parse("a: a\n").unwrap();
+        let cycle = looped.topological_order().unwrap_err();
+        assert_eq!(cycle.nodes, ids(&looped, "a"));
+    }
+
+    #[test]
+    fn count_paths_small_cases() {
+        let graph = Graph::parse("a: b c\nb: d\nc: d\nd: e\nx: a\n").unwrap();
+        let [a, d, e, x] = ids(&graph, "a d e x")[..] else {
+            unreachable!()
+        };
+        assert_eq!(graph.count_paths(a, e), Ok(2));
+        assert_eq!(graph.count_paths(x, d), Ok(2));
+        assert_eq!(graph.count_paths(e, a), Ok(0));
+        assert_eq!(graph.count_paths(a, a), Ok(1));
+        let to_e = graph.paths_to(e).unwrap();
+        assert_eq!(to_e[a.index()], 2);
+        assert_eq!(to_e[e.index()], 1);
+        assert_eq!(to_e[x.index()], 2);
+    }
+
+    #[test]
+    fn count_paths_only_minds_cycles_between_the_ends() {
+        let graph = Graph::parse("a: b\nb: c\nc: b d\nd: e\ne: d\ns: t\n").unwrap();
+        let [a, c, d, s, t] = ids(&graph, "a c d s t")[..] else {
+            unreachable!()
+        };
+        assert_eq!(graph.count_paths(s, t), Ok(1));
+        assert_eq!(graph.count_paths(a, s), Ok(0));
+        assert!(is_cycle(&graph, &graph.count_paths(a, c).unwrap_err()));
+        assert!(graph.count_paths(c, d).is_err());
+        assert!(graph.paths_to(t).is_ok());
+        assert!(graph.paths_to(d).is_err());
+    }
+
+    #[test]
+    fn count_paths_via_matches_walking_every_path() {
+        let mut state: u64 = 7;
+        let mut next = move |n: usize| {
+            state = state
+                .wrapping_mul(6364136223846793005)
+                .wrapping_add(1442695040888963407);
+            (state >> 33) as usize % n
+        };
+        for seed in 1..30 {
+            let graph = random_dag(10, seed);
+            let nodes: Vec<NodeId> = graph.nodes().collect();
+            let from = nodes[next(3)];
+            let to = nodes[7 + next(3)];
+            let waypoints: Vec<NodeId> = (0..next(4)).map(|_| nodes[next(10)]).collect();
+            let mut distinct = waypoints.clone();
+            distinct.sort();
+            distinct.dedup();
+            if distinct.len() < waypoints.len() {
+                continue;
+            }
+            assert_eq!(
+                graph.count_paths_via(from, to, &waypoints),
+                Ok(brute_force_paths(
+                    &graph,
+                    from,
+                    to,
+                    &waypoints,
+                    &mut Vec::new()
+                )),
+                "seed {} from {:?} to {:?} via {:?}",
+                seed,
+                from,
+                to,
+                waypoints
+            );
+        }
+    }
+
+    #[test]
+    fn count_paths_via_an_unreachable_waypoint_is_zero() {
+        let graph = Graph::parse("a: b\nb: c\nd: b\n").unwrap();
+        let [a, b, c, d] = ids(&graph, "a b c d")[..] else {
+            unreachable!()
+        };
+        assert_eq!(graph.count_paths_via(a, c, &[b]), Ok(1));
+        assert_eq!(graph.count_paths_via(a, c, &[d]), Ok(0));
+        assert_eq!(graph.count_paths_via(a, c, &[]), Ok(1));
+    }
+}
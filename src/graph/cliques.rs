@@ -0,0 +1,181 @@
+use super::{Graph, NodeId};
+use std::collections::HashSet;
+
+impl Graph {
+    /// The neighbours of every node with each edge taken both ways, leaving
+    /// out loops.
+    fn undirected_neighbors(&self) -> Vec<HashSet<usize>> {
+        let mut neighbors = vec![HashSet::new(); self.len()];
+        for (a, b) in self.edges().filter(|(a, b)| a != b) {
+            neighbors[a.0].insert(b.0);
+            neighbors[b.0].insert(a.0);
+        }
+        neighbors
+    }
+
+    /// Every maximal clique, taking each edge as undirected: sets of nodes
+    /// joined to each other that no other node is joined to all of. Found by
+    /// the Bron–Kerbosch algorithm with pivoting, each clique sorted.
+    pub fn maximal_cliques(&self) -> Vec<Vec<NodeId>> {
+        let neighbors = self.undirected_neighbors();
+        let mut cliques = Vec::new();
+        bron_kerbosch(
+            &neighbors,
+            &mut Vec::new(),
+            (0..self.len()).collect(),
+            Vec::new(),
+            &mut cliques,
+        );
+        cliques
+    }
+
+    /// The largest clique, taking each edge as undirected, the first found
+    /// among those of the same size.
+    pub fn maximum_clique(&self) -> Vec<NodeId> {
+        self.maximal_cliques()
+            .into_iter()
+            .rev()
+            .max_by_key(|clique| clique.len())
+            .unwrap_or_default()
+    }
+}
+
+/// Report every maximal clique that extends `clique` with some of
+/// `candidates` and none of `excluded`. Recursion is only as deep as the
+/// largest clique.
+fn bron_kerbosch(
+    neighbors: &[HashSet<usize>],
+    clique: &mut Vec<usize>,
+    mut candidates: Vec<usize>,
+    mut excluded: Vec<usize>,
+    cliques: &mut Vec<Vec<NodeId>>,
+) {
+    if candidates.is_empty() {
+        if excluded.is_empty() {
+            let mut found: Vec<NodeId> = clique.iter().map(|&node| NodeId(node)).collect();
+            found.sort();
+            cliques.push(found);
+        }
+        return;
+    }
+    // Any maximal clique includes the pivot or one of its non-neighbours,
+    // so only those need to be tried.
+    let pivot = *candidates
+        .iter()
+        .chain(&excluded)
+        .max_by_key(|&&node| {
+            candidates
+                .iter()
+                .filter(|candidate| neighbors[node].contains(candidate))
+                .count()
+        })
+        .expect("there are candidates");
+    let tries: Vec<usize> = candidates
+        .iter()
+        .copied()
+        .filter(|candidate| !neighbors[pivot].contains(candidate))
+        .collect();
+    for node in tries {
+        let joined = |set: &[usize]| -> Vec<usize> {
+            set.iter()
+                .copied()
+                .filter(|other| neighbors[node].contains(other))
+                .collect()
+        };
+        clique.push(node);
+        bron_kerbosch(
+            neighbors,
+            clique,
+            joined(&candidates),
+            joined(&excluded),
+            cliques,
+        );
+        clique.pop();
+        candidates.retain(|&candidate| candidate != node);
+        excluded.push(node);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn nodes(graph: &Graph, names: &str) -> Vec<NodeId> {
+        names
+            .split_whitespace()
+            .map(|name| graph.node(name).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn empty_graph_has_only_the_empty_clique() {
+        let graph = Graph::new();
+        assert_eq!(graph.maximal_cliques(), [Vec::<NodeId>::new()]);
+        assert!(graph.maximum_clique().is_empty());
+    }
+
+    #[test]
+    fn cliques_of_disconnected_pieces() {
+        let graph = Graph::parse("a: b c\nb: c\nc: d\nd: d\ne: f\ng:\n").unwrap();
+        let [a, b, c, d, e, f, g] = nodes(&graph, "a b c d e f g")[..] else {
+            unreachable!()
+        };
+        let mut cliques = graph.maximal_cliques();
+        cliques.sort();
+        assert_eq!(cliques, [vec![a, b, c], vec![c, d], vec![e, f], vec![g]]);
+        assert_eq!(graph.maximum_clique(), [a, b, c]);
+    }
+
+    #[test]
+    fn edges_count_whichever_way_they_point() {
+        let graph = Graph::parse("a: b\nb: c\nc: a\n").unwrap();
+        assert_eq!(graph.maximum_clique(), nodes(&graph, "a b c"));
+    }
+
+    #[test]
+    fn maximal_cliques_match_checking_every_set() {
+        let mut state: u64 = 0x9e37_79b9_7f4a_7c15;
+        for _ in 0..20 {
+            let mut graph = Graph::new();
+            let ids: Vec<NodeId> = (0..9).map(|i| graph.intern(&i.to_string())).collect();
+            let mut joined = [[false; 9]; 9];
+            for a in 0..9 {
+                for b in a + 1..9 {
+                    state ^= state << 13;
+                    state ^= state >> 7;
+                    state ^= state << 17;
+                    if state % 5 < 2 {
+                        graph.add_edge(ids[b], ids[a]);
+                        joined[a][b] = true;
+                        joined[b][a] = true;
+                    }
+                }
+            }
+            let is_clique = |set: u32| {
+                (0..9).all(|a| {
+                    (0..9).all(|b| {
+                        a == b || set & (1 << a) == 0 || set & (1 << b) == 0 || joined[a][b]
+                    })
+                })
+            };
+            let mut expected: Vec<Vec<NodeId>> = (1..1u32 << 9)
+                .filter(|&set| is_clique(set))
+                .filter(|&set| {
+                    (0..9).all(|extra| set & (1 << extra) != 0 || !is_clique(set | (1 << extra)))
+                })
+                .map(|set| {
+                    (0..9)
+                        .filter(|i| set & (1 << i) != 0)
+                        .map(|i| ids[i])
+                        .collect()
+                })
+                .collect();
+            expected.sort();
+            let mut cliques = graph.maximal_cliques();
+            cliques.sort();
+            assert_eq!(cliques, expected);
+            let largest = expected.iter().map(Vec::len).max().unwrap();
+            assert_eq!(graph.maximum_clique().len(), largest);
+        }
+    }
+}
@@ -0,0 +1,329 @@
+use super::{Graph, NodeId};
+
+/// The dominator tree of the nodes reachable from one node: node `a`
+/// dominates `b` if every path from the start to `b` passes through `a`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Dominators {
+    from: NodeId,
+    /// The immediate dominator of each reachable node, the start being its
+    /// own.
+    immediate: Vec<Option<NodeId>>,
+}
+
+impl Dominators {
+    /// The node the paths start from.
+    pub fn from(&self) -> NodeId {
+        self.from
+    }
+
+    /// The last node other than `node` that every path to it passes through,
+    /// `None` for the start and for nodes that can't be reached.
+    pub fn immediate(&self, node: NodeId) -> Option<NodeId> {
+        self.immediate[node.0].filter(|_| node != self.from)
+    }
+
+    /// Whether every path from the start to `b` passes through `a`. Every
+    /// reachable node dominates itself.
+    pub fn dominates(&self, a: NodeId, b: NodeId) -> bool {
+        self.must_pass(b).is_some_and(|nodes| nodes.contains(&a))
+    }
+
+    /// The nodes every path from the start to `to` passes through, in the
+    /// order they're passed, from the start to `to` itself. `None` if `to`
+    /// can't be reached.
+    pub fn must_pass(&self, to: NodeId) -> Option<Vec<NodeId>> {
+        self.immediate[to.0]?;
+        let mut nodes = vec![to];
+        let mut node = to;
+        while let Some(dominator) = self.immediate(node) {
+            nodes.push(dominator);
+            node = dominator;
+        }
+        nodes.reverse();
+        Some(nodes)
+    }
+}
+
+impl Graph {
+    /// The strongly connected components, each a set of nodes that can all
+    /// reach each other, by Tarjan's algorithm without recursion. A
+    /// component comes before every other component it has edges to.
+    pub fn strongly_connected_components(&self) -> Vec<Vec<NodeId>> {
+        let mut index = vec![None; self.len()];
+        let mut low = vec![0; self.len()];
+        let mut on_stack = vec![false; self.len()];
+        let mut stack = Vec::new();
+        let mut components = Vec::new();
+        let mut counter = 0;
+
+        for root in self.nodes() {
+            if index[root.0].is_some() {
+                continue;
+            }
+            // Each node being visited, with the next of its successors to
+            // look at.
+            let mut calls = vec![(root, 0)];
+            index[root.0] = Some(counter);
+            low[root.0] = counter;
+            counter += 1;
+            stack.push(root);
+            on_stack[root.0] = true;
+
+            while let Some(&mut (node, ref mut next)) = calls.last_mut() {
+                if let Some(&successor) = self.successors(node).get(*next) {
+                    *next += 1;
+                    match index[successor.0] {
+                        None => {
+                            index[successor.0] = Some(counter);
+                            low[successor.0] = counter;
+                            counter += 1;
+                            stack.push(successor);
+                            on_stack[successor.0] = true;
+                            calls.push((successor, 0));
+                        }
+                        Some(successor_index) if on_stack[successor.0] => {
+                            low[node.0] = low[node.0].min(successor_index);
+                        }
+                        Some(_) => {}
+                    }
+                    continue;
+                }
+
+                calls.pop();
+                if let Some(&(parent, _)) = calls.last() {
+                    low[parent.0] = low[parent.0].min(low[node.0]);
+                }
+                if Some(low[node.0]) == index[node.0] {
+                    let mut component = Vec::new();
+                    while let Some(member) = stack.pop() {
+                        on_stack[member.0] = false;
+                        component.push(member);
+                        if member == node {
+                            break;
+                        }
+                    }
+                    components.push(component);
+                }
+            }
+        }
+        // Tarjan's algorithm finds a component only after every component
+        // it reaches.
+        components.reverse();
+        components
+    }
+
+    /// The nodes reachable from `from`, in postorder of a depth-first search.
+    fn postorder(&self, from: NodeId) -> Vec<NodeId> {
+        let mut seen = vec![false; self.len()];
+        seen[from.0] = true;
+        let mut calls = vec![(from, 0)];
+        let mut order = Vec::new();
+        while let Some(&mut (node, ref mut next)) = calls.last_mut() {
+            if let Some(&successor) = self.successors(node).get(*next) {
+                *next += 1;
+                if !std::mem::replace(&mut seen[successor.0], true) {
+                    calls.push((successor, 0));
+                }
+            } else {
+                calls.pop();
+                order.push(node);
+            }
+        }
+        order
+    }
+
+    /// The dominators of every node reachable from `from`, with the
+    /// iterative algorithm of Cooper, Harvey and Kennedy.
+    pub fn dominators(&self, from: NodeId) -> Dominators {
+        let postorder = self.postorder(from);
+        let mut position = vec![usize::MAX; self.len()];
+        for (idx, node) in postorder.iter().enumerate() {
+            position[node.0] = idx;
+        }
+        let predecessors = self.reversed();
+
+        let mut immediate: Vec<Option<NodeId>> = vec![None; self.len()];
+        immediate[from.0] = Some(from);
+        let intersect = |immediate: &[Option<NodeId>], mut a: NodeId, mut b: NodeId| {
+            while a != b {
+                while position[a.0] < position[b.0] {
+                    a = immediate[a.0].expect("processed nodes have a dominator");
+                }
+                while position[b.0] < position[a.0] {
+                    b = immediate[b.0].expect("processed nodes have a dominator");
+                }
+            }
+            a
+        };
+        let mut changed = true;
+        while changed {
+            changed = false;
+            for &node in postorder.iter().rev().filter(|&&node| node != from) {
+                let dominator = predecessors
+                    .successors(node)
+                    .iter()
+                    .filter(|predecessor| immediate[predecessor.0].is_some())
+                    .fold(None, |dominator, &predecessor| match dominator {
+                        None => Some(predecessor),
+                        Some(dominator) => Some(intersect(&immediate, predecessor, dominator)),
+                    });
+                if dominator.is_some() && immediate[node.0] != dominator {
+                    immediate[node.0] = dominator;
+                    changed = true;
+                }
+            }
+        }
+        Dominators { from, immediate }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn nodes(graph: &Graph, names: &str) -> Vec<NodeId> {
+        names
+            .split_whitespace()
+            .map(|name| graph.node(name).unwrap())
+            .collect()
+    }
+
+    fn random_graph(nodes: usize, edges: usize, seed: u64) -> Graph {
+        let mut state = seed;
+        let mut next = move || {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            state as usize % nodes
+        };
+        let mut graph = Graph::new();
+        let ids: Vec<NodeId> = (0..nodes).map(|i| graph.intern(&i.to_string())).collect();
+        for _ in 0..edges {
+            let (a, b) = (next(), next());
+            graph.add_edge(ids[a], ids[b]);
+        }
+        graph
+    }
+
+    /// Which nodes `from` reaches without passing through `avoid`.
+    fn reach_avoiding(graph: &Graph, from: NodeId, avoid: Option<NodeId>) -> Vec<bool> {
+        let mut seen = vec![false; graph.len()];
+        seen[from.index()] = true;
+        let mut stack = vec![from];
+        while let Some(node) = stack.pop() {
+            for &next in graph.successors(node) {
+                if Some(next) != avoid && !std::mem::replace(&mut seen[next.index()], true) {
+                    stack.push(next);
+                }
+            }
+        }
+        seen
+    }
+
+    #[test]
+    fn components_of_a_small_graph() {
+        let graph = Graph::parse("a: b\nb: a c\nc: d\nd: e\ne: d\nf:\n").unwrap();
+        let [a, b, c, d, e, f] = nodes(&graph, "a b c d e f")[..] else {
+            unreachable!()
+        };
+        let mut components = graph.strongly_connected_components();
+        let position = |node: NodeId, components: &[Vec<NodeId>]| {
+            components
+                .iter()
+                .position(|component| component.contains(&node))
+        };
+        let (ab, cc, de) = (
+            position(a, &components),
+            position(c, &components),
+            position(d, &components),
+        );
+        assert!(ab < cc && cc < de);
+        for component in &mut components {
+            component.sort();
+        }
+        components.sort();
+        assert_eq!(components, [vec![a, b], vec![c], vec![d, e], vec![f]]);
+    }
+
+    #[test]
+    fn components_match_mutual_reachability() {
+        for seed in 1..20 {
+            let graph = random_graph(12, 16, seed);
+            let reach: Vec<Vec<bool>> = graph
+                .nodes()
+                .map(|node| reach_avoiding(&graph, node, None))
+                .collect();
+            let components = graph.strongly_connected_components();
+            let mut component = vec![usize::MAX; graph.len()];
+            for (idx, members) in components.iter().enumerate() {
+                for member in members {
+                    assert_eq!(component[member.index()], usize::MAX);
+                    component[member.index()] = idx;
+                }
+            }
+            for a in 0..graph.len() {
+                for b in 0..graph.len() {
+                    assert_eq!(component[a] == component[b], reach[a][b] && reach[b][a]);
+                }
+            }
+            for (a, b) in graph.edges() {
+                assert!(component[a.index()] <= component[b.index()]);
+            }
+        }
+    }
+
+    #[test]
+    fn empty_graph_has_no_components() {
+        assert!(Graph::new().strongly_connected_components().is_empty());
+    }
+
+    #[test]
+    fn dominators_of_a_diamond() {
+        let graph = Graph::parse("s: a b\na: c\nb: c\nc: d\nd: e\nx: d\n").unwrap();
+        let [s, a, c, d, e, x] = nodes(&graph, "s a c d e x")[..] else {
+            unreachable!()
+        };
+        let dominators = graph.dominators(s);
+        assert_eq!(dominators.from(), s);
+        assert_eq!(dominators.immediate(s), None);
+        assert_eq!(dominators.immediate(a), Some(s));
+        assert_eq!(dominators.immediate(c), Some(s));
+        assert_eq!(dominators.must_pass(e), Some(vec![s, c, d, e]));
+        assert_eq!(dominators.immediate(x), None);
+        assert!(dominators.dominates(c, e));
+        assert!(!dominators.dominates(a, c));
+        assert!(dominators.dominates(e, e));
+    }
+
+    #[test]
+    fn unreachable_nodes_have_no_dominators() {
+        let graph = Graph::parse("s: a\nb: c\nc: b\n").unwrap();
+        let [s, a, b] = nodes(&graph, "s a b")[..] else {
+            unreachable!()
+        };
+        let dominators = graph.dominators(s);
+        assert_eq!(dominators.must_pass(a), Some(vec![s, a]));
+        assert_eq!(dominators.immediate(b), None);
+        assert_eq!(dominators.must_pass(b), None);
+        assert!(!dominators.dominates(s, b));
+        assert!(!dominators.dominates(b, b));
+    }
+
+    #[test]
+    fn dominators_match_removing_each_node() {
+        for seed in 1..20 {
+            let graph = random_graph(10, 16, seed);
+            let from = NodeId(0);
+            let dominators = graph.dominators(from);
+            let reached = reach_avoiding(&graph, from, None);
+            for a in graph.nodes() {
+                let without = reach_avoiding(&graph, from, Some(a));
+                for b in graph.nodes() {
+                    let expected =
+                        reached[b.index()] && (a == b || a == from || !without[b.index()]);
+                    assert_eq!(dominators.dominates(a, b), expected, "seed {}", seed);
+                }
+            }
+        }
+    }
+}
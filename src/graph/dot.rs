@@ -0,0 +1,313 @@
+use super::{Graph, NodeId};
+use crate::grid::Path;
+use std::collections::HashSet;
+use std::fmt::Write;
+
+/// How [`Graph::to_dot`] draws a graph.
+#[derive(Debug, Clone, Default)]
+pub struct DotOptions {
+    /// Nodes to draw in red, like waypoints.
+    pub highlight_nodes: HashSet<NodeId>,
+    /// Edges to draw in red, like the steps of a path.
+    pub highlight_edges: HashSet<(NodeId, NodeId)>,
+    /// Draw each run of nodes with one edge in and one edge out as a single
+    /// edge labelled with how many nodes it stands for. Highlighted nodes are
+    /// always drawn.
+    pub collapse_chains: bool,
+    /// A path count to add to each node's label, indexed by
+    /// [`NodeId::index`], like the counts from [`Graph::paths_to`].
+    pub path_counts: Option<Vec<u128>>,
+}
+
+impl DotOptions {
+    /// Highlight every node of `path` and the edges between them.
+    pub fn highlight_path(&mut self, path: &[NodeId]) {
+        self.highlight_nodes.extend(path);
+        self.highlight_edges
+            .extend(path.windows(2).map(|step| (step[0], step[1])));
+    }
+}
+
+/// `text` escaped to go inside a quoted DOT string.
+fn escape(text: &str) -> String {
+    text.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+impl Graph {
+    /// The graph in Graphviz's DOT language, to render with e.g.
+    /// `dot -Tsvg`.
+    pub fn to_dot(&self, options: &DotOptions) -> String {
+        let mut incoming = vec![0; self.len()];
+        for (_, to) in self.edges() {
+            incoming[to.0] += 1;
+        }
+        let mut hidden: Vec<bool> = self
+            .nodes()
+            .map(|node| {
+                options.collapse_chains
+                    && incoming[node.0] == 1
+                    && self.successors(node).len() == 1
+                    && self.successors(node)[0] != node
+                    && !options.highlight_nodes.contains(&node)
+            })
+            .collect();
+        // Chains are followed from the nodes that are drawn, so a cycle made
+        // only of chain nodes would vanish: draw those in full.
+        let mut covered = vec![false; self.len()];
+        for node in self.nodes().filter(|node| !hidden[node.0]) {
+            for &next in self.successors(node) {
+                let mut current = next;
+                while hidden[current.0] && !covered[current.0] {
+                    covered[current.0] = true;
+                    current = self.successors(current)[0];
+                }
+            }
+        }
+        for node in self.nodes() {
+            hidden[node.0] &= covered[node.0];
+        }
+
+        let mut dot = String::from("digraph {\n");
+        for node in self.nodes().filter(|node| !hidden[node.0]) {
+            let mut label = escape(self.name(node));
+            if let Some(counts) = &options.path_counts {
+                write!(label, "\\n{}", counts[node.0]).unwrap();
+            }
+            let mut attributes = vec![format!("label=\"{}\"", label)];
+            if options.highlight_nodes.contains(&node) {
+                attributes.push("color=red".to_string());
+                attributes.push("penwidth=2".to_string());
+            }
+            writeln!(dot, "    n{} [{}];", node.0, attributes.join(", ")).unwrap();
+        }
+        for from in self.nodes().filter(|node| !hidden[node.0]) {
+            for &next in self.successors(from) {
+                let mut highlighted = options.highlight_edges.contains(&(from, next));
+                let mut skipped = 0;
+                let mut to = next;
+                while hidden[to.0] {
+                    let after = self.successors(to)[0];
+                    highlighted &= options.highlight_edges.contains(&(to, after));
+                    skipped += 1;
+                    to = after;
+                }
+                let mut attributes = Vec::new();
+                if skipped > 0 {
+                    let plural = if skipped == 1 { "" } else { "s" };
+                    attributes.push(format!("label=\"{} node{}\"", skipped, plural));
+                    attributes.push("style=dashed".to_string());
+                }
+                if highlighted {
+                    attributes.push("color=red".to_string());
+                    attributes.push("penwidth=2".to_string());
+                }
+                if attributes.is_empty() {
+                    writeln!(dot, "    n{} -> n{};", from.0, to.0).unwrap();
+                } else {
+                    let attributes = attributes.join(", ");
+                    writeln!(dot, "    n{} -> n{} [{}];", from.0, to.0, attributes).unwrap();
+                }
+            }
+        }
+        dot.push_str("}\n");
+        dot
+    }
+
+    /// The parent links behind `paths`, like those from
+    /// [`Grid::find_paths`](crate::grid::Grid::find_paths), as a graph with
+    /// an edge from each cell to the next, nodes being named by position.
+    pub fn search_tree(paths: &[Path]) -> Graph {
+        let mut graph = Graph::new();
+        let mut edges = HashSet::new();
+        for path in paths {
+            let cells = path.cells();
+            let nodes: Vec<NodeId> = cells
+                .iter()
+                .map(|cell| graph.intern(&cell.position.to_string()))
+                .collect();
+            for step in nodes.windows(2) {
+                if edges.insert((step[0], step[1])) {
+                    graph.add_edge(step[0], step[1]);
+                }
+            }
+        }
+        graph
+    }
+
+    /// The nodes of a [`Graph::search_tree`] that `path` runs through, to
+    /// highlight with [`DotOptions::highlight_path`].
+    pub fn search_path(&self, path: &Path) -> Vec<NodeId> {
+        path.cells()
+            .iter()
+            .filter_map(|cell| self.node(&cell.position.to_string()))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::grid::{Grid, GridPosition, SearchConfig, SearchMode};
+
+    fn nodes(graph: &Graph, names: &str) -> Vec<NodeId> {
+        names
+            .split_whitespace()
+            .map(|name| graph.node(name).unwrap())
+            .collect()
+    }
+
+    fn collapsed() -> DotOptions {
+        DotOptions {
+            collapse_chains: true,
+            ..DotOptions::default()
+        }
+    }
+
+    #[test]
+    fn plain_graph() {
+        let graph = Graph::parse("a: b c\nb: c\n").unwrap();
+        let dot = graph.to_dot(&DotOptions::default());
+        assert_eq!(
+            dot,
+            r#"digraph {
+    n0 [label="a"];
+    n1 [label="b"];
+    n2 [label="c"];
+    n0 -> n1;
+    n0 -> n2;
+    n1 -> n2;
+}
+"#
+        );
+        assert_eq!(Graph::new().to_dot(&collapsed()), "digraph {\n}\n");
+    }
+
+    #[test]
+    fn names_are_escaped() {
+        let mut graph = Graph::new();
+        graph.intern(r#"say "hi" \o/"#);
+        let dot = graph.to_dot(&DotOptions::default());
+        assert!(dot.contains(r#"n0 [label="say \"hi\" \\o/"];"#), "{}", dot);
+    }
+
+    #[test]
+    fn path_counts_go_in_the_labels() {
+        let graph = Graph::parse("a: b c\nb: c\n").unwrap();
+        let options = DotOptions {
+            path_counts: Some(graph.paths_to(graph.node("c").unwrap()).unwrap()),
+            ..DotOptions::default()
+        };
+        let dot = graph.to_dot(&options);
+        assert!(dot.contains(r#"n0 [label="a\n2"];"#), "{}", dot);
+        assert!(dot.contains(r#"n2 [label="c\n1"];"#), "{}", dot);
+    }
+
+    #[test]
+    fn highlighted_path_is_drawn_in_red() {
+        let graph = Graph::parse("a: b c\nb: c\n").unwrap();
+        let mut options = DotOptions::default();
+        options.highlight_path(&nodes(&graph, "a b c"));
+        let dot = graph.to_dot(&options);
+        assert!(dot.contains(r#"n1 [label="b", color=red, penwidth=2];"#));
+        assert!(dot.contains("n0 -> n1 [color=red, penwidth=2];"));
+        assert!(dot.contains("n0 -> n2;\n"));
+    }
+
+    #[test]
+    fn chains_collapse_into_one_labelled_edge() {
+        let graph = Graph::parse("a: b x\nb: c\nc: d\nx: d\n").unwrap();
+        let dot = graph.to_dot(&collapsed());
+        assert_eq!(
+            dot,
+            r#"digraph {
+    n0 [label="a"];
+    n4 [label="d"];
+    n0 -> n4 [label="2 nodes", style=dashed];
+    n0 -> n4 [label="1 node", style=dashed];
+}
+"#
+        );
+    }
+
+    #[test]
+    fn highlighted_nodes_break_chains() {
+        let graph = Graph::parse("a: b\nb: c\nc: d\n").unwrap();
+        let mut options = collapsed();
+        options.highlight_nodes.insert(graph.node("b").unwrap());
+        let dot = graph.to_dot(&options);
+        assert!(dot.contains("n0 -> n1;\n"), "{}", dot);
+        assert!(
+            dot.contains(r#"n1 -> n3 [label="1 node", style=dashed];"#),
+            "{}",
+            dot
+        );
+        assert!(!dot.contains("n2 ["));
+    }
+
+    #[test]
+    fn collapsed_edge_is_red_only_if_the_whole_chain_is() {
+        let graph = Graph::parse("a: b\nb: c\nc: d\n").unwrap();
+        let [a, b, c, d] = nodes(&graph, "a b c d")[..] else {
+            unreachable!()
+        };
+        let mut options = collapsed();
+        options.highlight_edges.extend([(a, b), (b, c)]);
+        assert!(
+            graph
+                .to_dot(&options)
+                .contains(r#"n0 -> n3 [label="2 nodes", style=dashed];"#)
+        );
+        options.highlight_edges.insert((c, d));
+        let dot = graph.to_dot(&options);
+        assert!(
+            dot.contains(r#"n0 -> n3 [label="2 nodes", style=dashed, color=red, penwidth=2];"#)
+        );
+    }
+
+    #[test]
+    fn cycles_of_chain_nodes_are_drawn_in_full() {
+        let graph = Graph::parse("a: b\nb: a\nc: c\n").unwrap();
+        let dot = graph.to_dot(&collapsed());
+        assert_eq!(
+            dot,
+            r#"digraph {
+    n0 [label="a"];
+    n1 [label="b"];
+    n2 [label="c"];
+    n0 -> n1;
+    n1 -> n0;
+    n2 -> n2;
+}
+"#
+        );
+    }
+
+    #[test]
+    fn search_tree_follows_each_path_once() {
+        let grid: Grid = "S.E\nS.E\n".parse().unwrap();
+        let config = SearchConfig {
+            starting_cells: grid.find_cells(|cell, _| cell.value == 'S'),
+            mode: SearchMode::BFS,
+            first_path: false,
+        };
+        let paths = grid.find_paths(
+            config,
+            |cell, next, _| next.position.1 == cell.position.1 + 1,
+            |cell, _| cell.value == 'E',
+        );
+        assert_eq!(paths.len(), 2);
+        let doubled: Vec<Path> = paths.iter().chain(&paths).cloned().collect();
+        let tree = Graph::search_tree(&doubled);
+        assert_eq!(tree.len(), 6);
+        assert_eq!(tree.edges().count(), 4);
+
+        let path = tree.search_path(&paths[1]);
+        let names: Vec<&str> = path.iter().map(|&node| tree.name(node)).collect();
+        let row = paths[1].cells()[0].position.0;
+        let expected: Vec<String> = (0..3)
+            .map(|col| GridPosition(row, col).to_string())
+            .collect();
+        assert_eq!(names, expected);
+        assert!(Graph::new().search_path(&paths[0]).is_empty());
+    }
+}
@@ -0,0 +1,276 @@
+use super::{Graph, NodeId};
+use std::collections::VecDeque;
+
+/// A maximum flow between two nodes, and the minimum cut that limits it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Flow {
+    /// How much flows from the source to the sink. A `u128`, since the
+    /// capacities out of the source can add up to more than a `u64` holds.
+    pub value: u128,
+    /// The flow along each edge that carries any, as `(from, to, flow)`.
+    pub edges: Vec<(NodeId, NodeId, u64)>,
+    /// The nodes still reachable from the source once the flow is sent:
+    /// the source's side of the minimum cut.
+    pub source_side: Vec<NodeId>,
+    /// The edges from the source's side to the rest, whose capacities add up
+    /// to the flow.
+    pub cut: Vec<(NodeId, NodeId)>,
+}
+
+/// An edge of the residual network, paired with its reverse at `idx ^ 1`.
+/// The two always add up to the original edge's capacity, so neither can
+/// overflow.
+#[derive(Debug, Clone, Copy)]
+struct Residual {
+    to: NodeId,
+    capacity: u64,
+}
+
+impl Graph {
+    /// The maximum flow from `source` to `sink` with each edge `(a, b)`
+    /// carrying at most `capacity(a, b)`, by the Edmonds–Karp algorithm:
+    /// keep sending flow along a shortest path with room to spare.
+    pub fn max_flow(
+        &self,
+        source: NodeId,
+        sink: NodeId,
+        capacity: impl Fn(NodeId, NodeId) -> u64,
+    ) -> Flow {
+        assert_ne!(source, sink, "flow needs a source and sink that differ");
+        let mut residuals = Vec::new();
+        let mut outgoing = vec![Vec::new(); self.len()];
+        for (from, to) in self.edges() {
+            outgoing[from.0].push(residuals.len());
+            residuals.push(Residual {
+                to,
+                capacity: capacity(from, to),
+            });
+            outgoing[to.0].push(residuals.len());
+            residuals.push(Residual {
+                to: from,
+                capacity: 0,
+            });
+        }
+        let original: Vec<u64> = residuals.iter().map(|edge| edge.capacity).collect();
+
+        // The residual edge each node was first reached by, searching from
+        // the source.
+        let search = |residuals: &[Residual]| {
+            let mut reached_by = vec![None; self.len()];
+            let mut seen = vec![false; self.len()];
+            seen[source.0] = true;
+            let mut queue = VecDeque::from([source]);
+            while let Some(node) = queue.pop_front() {
+                for &edge in &outgoing[node.0] {
+                    let Residual { to, capacity } = residuals[edge];
+                    if capacity > 0 && !std::mem::replace(&mut seen[to.0], true) {
+                        reached_by[to.0] = Some(edge);
+                        queue.push_back(to);
+                    }
+                }
+            }
+            (seen, reached_by)
+        };
+
+        let mut value = 0;
+        let source_side = loop {
+            let (seen, reached_by) = search(&residuals);
+            if !seen[sink.0] {
+                break seen;
+            }
+            let mut path = Vec::new();
+            let mut node = sink;
+            while let Some(edge) = reached_by[node.0] {
+                path.push(edge);
+                node = residuals[edge ^ 1].to;
+            }
+            let sent = path
+                .iter()
+                .map(|&edge| residuals[edge].capacity)
+                .min()
+                .expect("the sink isn't the source");
+            for edge in path {
+                residuals[edge].capacity -= sent;
+                residuals[edge ^ 1].capacity = residuals[edge ^ 1]
+                    .capacity
+                    .checked_add(sent)
+                    .expect("an edge and its reverse add up to its capacity");
+            }
+            value += u128::from(sent);
+        };
+
+        let mut edges = Vec::new();
+        let mut cut = Vec::new();
+        for (idx, (from, to)) in self.edges().enumerate() {
+            let forward = 2 * idx;
+            let flow = original[forward] - residuals[forward].capacity;
+            if flow > 0 {
+                edges.push((from, to, flow));
+            }
+            if source_side[from.0] && !source_side[to.0] {
+                cut.push((from, to));
+            }
+        }
+        Flow {
+            value,
+            edges,
+            source_side: self.nodes().filter(|node| source_side[node.0]).collect(),
+            cut,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn nodes(graph: &Graph, names: &str) -> Vec<NodeId> {
+        names
+            .split_whitespace()
+            .map(|name| graph.node(name).unwrap())
+            .collect()
+    }
+
+    /// Check that `flow` keeps to the capacities, is conserved at every node
+    /// but the ends, and is as large as its cut.
+    fn check_flow(
+        graph: &Graph,
+        flow: &Flow,
+        source: NodeId,
+        sink: NodeId,
+        capacity: impl Fn(NodeId, NodeId) -> u64,
+    ) {
+        let mut balance = vec![0i128; graph.len()];
+        for &(from, to, amount) in &flow.edges {
+            assert!(amount > 0 && amount <= capacity(from, to));
+            balance[from.index()] -= i128::from(amount);
+            balance[to.index()] += i128::from(amount);
+        }
+        for node in graph.nodes() {
+            let expected = match node {
+                node if node == source => -(flow.value as i128),
+                node if node == sink => flow.value as i128,
+                _ => 0,
+            };
+            assert_eq!(balance[node.index()], expected);
+        }
+        assert!(flow.source_side.contains(&source));
+        assert!(!flow.source_side.contains(&sink));
+        let cut: u128 = flow
+            .cut
+            .iter()
+            .map(|&(from, to)| u128::from(capacity(from, to)))
+            .sum();
+        assert_eq!(cut, flow.value);
+    }
+
+    #[test]
+    fn flow_through_a_small_network() {
+        let graph = Graph::parse("s: a b\na: b t\nb: t\n").unwrap();
+        let [s, a, b, t] = nodes(&graph, "s a b t")[..] else {
+            unreachable!()
+        };
+        let capacities = HashMap::from([
+            ((s, a), 3),
+            ((s, b), 2),
+            ((a, b), 2),
+            ((a, t), 2),
+            ((b, t), 3),
+        ]);
+        let capacity = |from, to| capacities[&(from, to)];
+        let flow = graph.max_flow(s, t, capacity);
+        assert_eq!(flow.value, 5);
+        assert_eq!(flow.source_side, [s]);
+        assert_eq!(flow.cut, [(s, a), (s, b)]);
+        check_flow(&graph, &flow, s, t, capacity);
+    }
+
+    #[test]
+    fn unreachable_sink_gets_no_flow() {
+        let graph = Graph::parse("s: a\nb: t\nt: s\n").unwrap();
+        let [s, a, t] = nodes(&graph, "s a t")[..] else {
+            unreachable!()
+        };
+        let flow = graph.max_flow(s, t, |_, _| 7);
+        assert_eq!(flow.value, 0);
+        assert!(flow.edges.is_empty());
+        assert!(flow.cut.is_empty());
+        assert_eq!(flow.source_side, [s, a]);
+    }
+
+    #[test]
+    fn zero_capacities_cut_the_sink_off() {
+        let graph = Graph::parse("s: t\n").unwrap();
+        let [s, t] = nodes(&graph, "s t")[..] else {
+            unreachable!()
+        };
+        let flow = graph.max_flow(s, t, |_, _| 0);
+        assert_eq!(flow.value, 0);
+        assert_eq!(flow.cut, [(s, t)]);
+    }
+
+    #[test]
+    fn flow_can_exceed_a_u64() {
+        let graph = Graph::parse("s: a b t\na: t\nb: t\n").unwrap();
+        let [s, t] = nodes(&graph, "s t")[..] else {
+            unreachable!()
+        };
+        let flow = graph.max_flow(s, t, |_, _| u64::MAX);
+        assert_eq!(flow.value, 3 * u128::from(u64::MAX));
+        check_flow(&graph, &flow, s, t, |_, _| u64::MAX);
+    }
+
+    #[test]
+    #[should_panic(expected = "source and sink that differ")]
+    fn source_must_not_be_the_sink() {
+        let graph = Graph::parse("s: s\n").unwrap();
+        let s = graph.node("s").unwrap();
+        graph.max_flow(s, s, |_, _| 1);
+    }
+
+    #[test]
+    fn flow_matches_the_smallest_cut() {
+        let mut state: u64 = 0x2545_f491_4f6c_dd1d;
+        let mut next = move |n: u64| {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            state % n
+        };
+        for _ in 0..30 {
+            let mut graph = Graph::new();
+            let ids: Vec<NodeId> = (0..7).map(|i| graph.intern(&i.to_string())).collect();
+            let mut capacities = HashMap::new();
+            for _ in 0..14 {
+                let (a, b) = (ids[next(7) as usize], ids[next(7) as usize]);
+                if a != b && !capacities.contains_key(&(a, b)) {
+                    capacities.insert((a, b), next(10));
+                    graph.add_edge(a, b);
+                }
+            }
+            let capacity = |from, to| capacities[&(from, to)];
+            let (source, sink) = (ids[0], ids[6]);
+            let flow = graph.max_flow(source, sink, capacity);
+            check_flow(&graph, &flow, source, sink, capacity);
+
+            // Every set of nodes with the source but not the sink.
+            let smallest = (0..1u32 << 5)
+                .map(|inner| {
+                    let side = |node: NodeId| match node.index() {
+                        0 => true,
+                        6 => false,
+                        i => inner & (1 << (i - 1)) != 0,
+                    };
+                    graph
+                        .edges()
+                        .filter(|&(from, to)| side(from) && !side(to))
+                        .map(|(from, to)| u128::from(capacity(from, to)))
+                        .sum::<u128>()
+                })
+                .min()
+                .unwrap();
+            assert_eq!(flow.value, smallest);
+        }
+    }
+}
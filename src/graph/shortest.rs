@@ -0,0 +1,227 @@
+use super::{Graph, NodeId};
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, VecDeque};
+
+/// The shortest paths from one node to every node it can reach, as the
+/// distance to each and its parent on a shortest path.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ShortestPaths {
+    from: NodeId,
+    distances: Vec<Option<u64>>,
+    parents: Vec<Option<NodeId>>,
+}
+
+impl ShortestPaths {
+    fn new(graph: &Graph, from: NodeId) -> Self {
+        let mut distances = vec![None; graph.len()];
+        distances[from.0] = Some(0);
+        ShortestPaths {
+            from,
+            distances,
+            parents: vec![None; graph.len()],
+        }
+    }
+
+    /// The node the paths start from.
+    pub fn from(&self) -> NodeId {
+        self.from
+    }
+
+    /// The length of the shortest path to `node`, `None` if it can't be
+    /// reached.
+    pub fn distance(&self, node: NodeId) -> Option<u64> {
+        self.distances[node.0]
+    }
+
+    /// The node before `node` on its shortest path, `None` for the start and
+    /// for nodes that can't be reached.
+    pub fn parent(&self, node: NodeId) -> Option<NodeId> {
+        self.parents[node.0]
+    }
+
+    /// The nodes along the shortest path to `to`, from the start to `to`.
+    pub fn path(&self, to: NodeId) -> Option<Vec<NodeId>> {
+        self.distance(to)?;
+        let mut path = vec![to];
+        let mut node = to;
+        while let Some(parent) = self.parent(node) {
+            path.push(parent);
+            node = parent;
+        }
+        path.reverse();
+        Some(path)
+    }
+}
+
+impl Graph {
+    /// The shortest paths from `from`, counting every edge as one step.
+    pub fn bfs(&self, from: NodeId) -> ShortestPaths {
+        let mut paths = ShortestPaths::new(self, from);
+        let mut queue = VecDeque::from([from]);
+        while let Some(node) = queue.pop_front() {
+            let distance = paths.distances[node.0].expect("queued nodes are reached");
+            for &next in self.successors(node) {
+                if paths.distances[next.0].is_none() {
+                    paths.distances[next.0] = Some(distance + 1);
+                    paths.parents[next.0] = Some(node);
+                    queue.push_back(next);
+                }
+            }
+        }
+        paths
+    }
+
+    /// The shortest paths from `from`, with each edge `(a, b)` as long as
+    /// `weight(a, b)`, by Dijkstra's algorithm. A path longer than `u64::MAX`
+    /// is never taken, so a node only reachable that way has no distance.
+    pub fn dijkstra(&self, from: NodeId, weight: impl Fn(NodeId, NodeId) -> u64) -> ShortestPaths {
+        let mut paths = ShortestPaths::new(self, from);
+        let mut done = vec![false; self.len()];
+        let mut queue = BinaryHeap::from([Reverse((0u64, from))]);
+        while let Some(Reverse((distance, node))) = queue.pop() {
+            if std::mem::replace(&mut done[node.0], true) {
+                continue;
+            }
+            for &next in self.successors(node) {
+                let Some(through) = distance.checked_add(weight(node, next)) else {
+                    continue;
+                };
+                if paths.distances[next.0].is_none_or(|known| through < known) {
+                    paths.distances[next.0] = Some(through);
+                    paths.parents[next.0] = Some(node);
+                    queue.push(Reverse((through, next)));
+                }
+            }
+        }
+        paths
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn nodes(graph: &Graph, names: &str) -> Vec<NodeId> {
+        names
+            .split_whitespace()
+            .map(|name| graph.node(name).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn bfs_counts_steps() {
+        let graph = Graph::parse("a: b c\nb: d\nc: d\nd: e\nx: a\n").unwrap();
+        let [a, b, d, e, x] = nodes(&graph, "a b d e x")[..] else {
+            unreachable!()
+        };
+        let paths = graph.bfs(a);
+        assert_eq!(paths.from(), a);
+        assert_eq!(paths.distance(a), Some(0));
+        assert_eq!(paths.distance(e), Some(3));
+        assert_eq!(paths.path(e), Some(vec![a, b, d, e]));
+        assert_eq!(paths.path(a), Some(vec![a]));
+        assert_eq!(paths.parent(a), None);
+        assert_eq!(paths.distance(x), None);
+    }
+
+    #[test]
+    fn unreachable_nodes_have_no_distance_or_path() {
+        let graph = Graph::parse("a: b\nc: a\nd: d\n").unwrap();
+        let [a, c, d] = nodes(&graph, "a c d")[..] else {
+            unreachable!()
+        };
+        for paths in [graph.bfs(a), graph.dijkstra(a, |_, _| 1)] {
+            for node in [c, d] {
+                assert_eq!(paths.distance(node), None);
+                assert_eq!(paths.parent(node), None);
+                assert_eq!(paths.path(node), None);
+            }
+        }
+    }
+
+    #[test]
+    fn dijkstra_takes_the_lighter_longer_path() {
+        let graph = Graph::parse("a: b d\nb: c\nc: e\nd: e\n").unwrap();
+        let [a, b, c, d, e] = nodes(&graph, "a b c d e")[..] else {
+            unreachable!()
+        };
+        let weight = |from: NodeId, _: NodeId| if from == d || from == a { 5 } else { 1 };
+        let paths = graph.dijkstra(a, weight);
+        assert_eq!(paths.distance(e), Some(7));
+        assert_eq!(paths.path(e), Some(vec![a, b, c, e]));
+        assert_eq!(paths.distance(d), Some(5));
+        assert_eq!(graph.bfs(a).path(e), Some(vec![a, d, e]));
+    }
+
+    #[test]
+    fn dijkstra_skips_paths_too_long_to_count() {
+        let graph = Graph::parse("a: b c\nb: d\nc: d\nd: e\n").unwrap();
+        let [a, b, c, d, e] = nodes(&graph, "a b c d e")[..] else {
+            unreachable!()
+        };
+        // Through `b` overflows, through `c` just fits, and `e` is beyond.
+        let weight = |from: NodeId, _: NodeId| {
+            if from == a {
+                u64::MAX - 1
+            } else if from == b {
+                2
+            } else {
+                1
+            }
+        };
+        let paths = graph.dijkstra(a, weight);
+        assert_eq!(paths.distance(b), Some(u64::MAX - 1));
+        assert_eq!(paths.distance(d), Some(u64::MAX));
+        assert_eq!(paths.path(d), Some(vec![a, c, d]));
+        assert_eq!(paths.distance(e), None);
+        assert_eq!(paths.path(e), None);
+    }
+
+    #[test]
+    fn dijkstra_matches_relaxing_every_edge() {
+        let mut state: u64 = 0x9e37_79b9_7f4a_7c15;
+        let mut next = move |n: u64| {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            state % n
+        };
+        for _ in 0..20 {
+            let mut graph = Graph::new();
+            let ids: Vec<NodeId> = (0..10).map(|i| graph.intern(&i.to_string())).collect();
+            let mut weights = vec![vec![0; 10]; 10];
+            for _ in 0..25 {
+                let (a, b) = (next(10) as usize, next(10) as usize);
+                if weights[a][b] == 0 {
+                    weights[a][b] = 1 + next(9);
+                    graph.add_edge(ids[a], ids[b]);
+                }
+            }
+            let paths = graph.dijkstra(ids[0], |a, b| weights[a.index()][b.index()]);
+
+            // Bellman–Ford.
+            let mut expected = [None; 10];
+            expected[0] = Some(0);
+            for _ in 0..10 {
+                for (a, b) in graph.edges() {
+                    if let Some(distance) = expected[a.index()] {
+                        let through = distance + weights[a.index()][b.index()];
+                        if expected[b.index()].is_none_or(|known| through < known) {
+                            expected[b.index()] = Some(through);
+                        }
+                    }
+                }
+            }
+            for &node in &ids {
+                assert_eq!(paths.distance(node), expected[node.index()]);
+                if let Some(path) = paths.path(node) {
+                    let length: u64 = path
+                        .windows(2)
+                        .map(|pair| weights[pair[0].index()][pair[1].index()])
+                        .sum();
+                    assert_eq!(Some(length), expected[node.index()]);
+                }
+            }
+        }
+    }
+}
@@ -1,3 +1,4 @@
+use crate::error::{Error, Result};
 use std::collections::{HashSet, VecDeque};
 use std::fmt::{Display, Formatter};
 use std::str::FromStr;
@@ -64,11 +65,18 @@ impl Path {
         let cells: Vec<GridCell> = self.into();
         cells.contains(&cell)
     }
+
+    /// The cells along the path, from its start to its end.
+    pub fn cells(&self) -> Vec<GridCell> {
+        let mut cells: Vec<GridCell> = self.into();
+        cells.reverse();
+        cells
+    }
 }
 
-impl Into<Vec<GridCell>> for Path {
-    fn into(self) -> Vec<GridCell> {
-        let mut current = self;
+impl From<Path> for Vec<GridCell> {
+    fn from(path: Path) -> Vec<GridCell> {
+        let mut current = path;
         let mut path: Vec<GridCell> = Vec::new();
         while let Some(parent) = current.parent {
             path.push(current.cell);
@@ -79,9 +87,9 @@ impl Into<Vec<GridCell>> for Path {
     }
 }
 
-impl Into<Vec<GridCell>> for &Path {
-    fn into(self) -> Vec<GridCell> {
-        let mut current = self.clone();
+impl From<&Path> for Vec<GridCell> {
+    fn from(path: &Path) -> Vec<GridCell> {
+        let mut current = path.clone();
         let mut path: Vec<GridCell> = Vec::new();
         while let Some(parent) = current.parent {
             path.push(current.cell);
@@ -111,23 +119,17 @@ pub struct Grid {
     dimensions: Dimensions,
 }
 
-impl Into<Grid> for (usize, usize) {
-    fn into(self) -> Grid {
-        let mut grid: Vec<Vec<char>> = Vec::new();
-        for r in 0..self.0 {
-            grid.push(Vec::new());
-            for _ in 0..self.1 {
-                grid[r].push('.');
-            }
-        }
-        Grid::new(grid, (self.0, self.1))
+impl From<(usize, usize)> for Grid {
+    fn from(dimensions: (usize, usize)) -> Grid {
+        let grid = vec![vec!['.'; dimensions.1]; dimensions.0];
+        Grid::new(grid, dimensions)
     }
 }
 
 impl Grid {
     pub fn new(grid: Vec<Vec<char>>, dimensions: (usize, usize)) -> Self {
         Grid {
-            grid: grid,
+            grid,
             dimensions: Dimensions {
                 rows: dimensions.0,
                 cols: dimensions.1,
@@ -164,16 +166,11 @@ impl Grid {
     }
 
     pub fn get_cell(&self, position: GridPosition) -> Option<GridCell> {
-        match self.grid.get(position.0) {
-            Some(row) => match row.get(position.1) {
-                Some(value) => Some(GridCell {
-                    value: *value,
-                    position,
-                }),
-                None => None,
-            },
-            None => None,
-        }
+        let value = self.grid.get(position.0)?.get(position.1)?;
+        Some(GridCell {
+            value: *value,
+            position,
+        })
     }
 
     pub fn iter_cells(&self) -> impl Iterator<Item = GridCell> {
@@ -329,7 +326,7 @@ impl Grid {
                 }
             }
 
-            if goal_predicate(&path.cell, &self) {
+            if goal_predicate(&path.cell, self) {
                 paths.push(path);
                 if config.first_path {
                     return paths;
@@ -364,27 +361,25 @@ impl Display for Grid {
     }
 }
 
-#[derive(Debug)]
-pub enum ParseError {
-    InvalidGrid(String),
-}
-
 impl FromStr for Grid {
-    type Err = ParseError;
+    type Err = Error;
 
-    fn from_str(input: &str) -> Result<Self, ParseError> {
+    fn from_str(input: &str) -> Result<Self> {
         let mut grid: Vec<Vec<char>> = Vec::new();
-        let row_len: Option<i32> = None;
         for (r, row) in input.lines().enumerate() {
-            grid.push(Vec::new());
-            if let Some(len) = row_len
-                && row.len() as i32 != len
+            if let Some(first) = grid.first()
+                && row.chars().count() != first.len()
             {
-                return Err(ParseError::InvalidGrid("Grid has unequal columns".into()));
-            }
-            for (_, col) in row.chars().enumerate() {
-                grid[r].push(col);
+                return Err(Error::parse(
+                    r + 1,
+                    format!("a grid row of {} cells", first.len()),
+                    row,
+                ));
             }
+            grid.push(row.chars().collect());
+        }
+        if grid.is_empty() {
+            return Err(Error::parse(1, "at least one grid row", ""));
         }
         let dimensions = Dimensions {
             rows: grid.len(),
@@ -0,0 +1,501 @@
+use std::fmt::{Display, Formatter};
+
+/// An [`IntegerSystem`] has no solution in non-negative integers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Infeasible;
+
+impl Display for Infeasible {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "the equations have no non-negative integer solution")
+    }
+}
+
+/// Linear equations `a · x = b` with non-negative integer coefficients, to
+/// be solved in non-negative integers.
+///
+/// Small systems only: Gaussian elimination over the rationals leaves some
+/// free variables, and every value of those up to the bound the equations
+/// put on them is tried.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IntegerSystem {
+    variables: usize,
+    equations: Vec<(Vec<u64>, u64)>,
+}
+
+impl IntegerSystem {
+    pub fn new(variables: usize) -> Self {
+        IntegerSystem {
+            variables,
+            equations: Vec::new(),
+        }
+    }
+
+    pub fn variables(&self) -> usize {
+        self.variables
+    }
+
+    /// Add the equation that `coefficients · x = value`.
+    pub fn equation(&mut self, coefficients: Vec<u64>, value: u64) {
+        assert_eq!(
+            coefficients.len(),
+            self.variables,
+            "an equation needs a coefficient for every variable"
+        );
+        self.equations.push((coefficients, value));
+    }
+
+    /// The largest value each variable can take: no more than any equation
+    /// it appears in allows. A variable in no equation is left at zero, as it
+    /// only ever adds to the sum.
+    fn bounds(&self) -> Vec<(i64, i64)> {
+        (0..self.variables)
+            .map(|variable| {
+                let largest = self
+                    .equations
+                    .iter()
+                    .filter(|(coefficients, _)| coefficients[variable] > 0)
+                    .map(|(coefficients, value)| value / coefficients[variable])
+                    .min()
+                    .unwrap_or(0);
+                (0, to_i64(largest))
+            })
+            .collect()
+    }
+
+    /// The non-negative integer solution with the smallest sum of variables.
+    pub fn minimise_sum(&self) -> Result<Vec<u64>, Infeasible> {
+        let equalities = self
+            .equations
+            .iter()
+            .map(|(coefficients, value)| {
+                (
+                    coefficients.iter().map(|&c| to_i64(c)).collect(),
+                    to_i64(*value),
+                )
+            })
+            .collect();
+        let program = Program {
+            variables: self.variables,
+            equalities,
+            inequalities: Vec::new(),
+            bounds: self.bounds(),
+            objective: vec![1; self.variables],
+        };
+        let values = optimise(&program).ok_or(Infeasible)?;
+        Ok(values.into_iter().map(|value| value as u64).collect())
+    }
+}
+
+fn to_i64(value: u64) -> i64 {
+    i64::try_from(value).expect("coefficients and values fit in an i64")
+}
+
+/// An integer program with every variable bounded: minimise
+/// `objective · x` subject to the equalities `a · x = b`, the inequalities
+/// `a · x ≤ b` and `lower ≤ x ≤ upper`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Program {
+    pub variables: usize,
+    pub equalities: Vec<(Vec<i64>, i64)>,
+    pub inequalities: Vec<(Vec<i64>, i64)>,
+    pub bounds: Vec<(i64, i64)>,
+    pub objective: Vec<i64>,
+}
+
+/// An equality after elimination, `pivot · x[variable] + Σ free · x = value`
+/// over the free variables.
+#[derive(Debug, Clone)]
+struct Row {
+    variable: usize,
+    pivot: i128,
+    free: Vec<i128>,
+    value: i128,
+}
+
+impl Row {
+    /// The value of the pivot variable once every free variable it depends
+    /// on is set, `None` unless it's an integer.
+    fn solve(&self, free_values: &[i64]) -> Option<i64> {
+        let rest: i128 = self
+            .free
+            .iter()
+            .zip(free_values)
+            .map(|(&coefficient, &value)| coefficient * i128::from(value))
+            .sum();
+        let numerator = self.value - rest;
+        if numerator % self.pivot != 0 {
+            return None;
+        }
+        i64::try_from(numerator / self.pivot).ok()
+    }
+}
+
+/// Reduce the equalities to rows solving for pivot variables in terms of the
+/// free ones, with fraction-free elimination, returning the rows and the free
+/// variables. `None` if the equalities contradict each other.
+fn eliminate(program: &Program) -> Option<(Vec<Row>, Vec<usize>)> {
+    let mut rows: Vec<(Vec<i128>, i128)> = program
+        .equalities
+        .iter()
+        .map(|(coefficients, value)| {
+            let coefficients = coefficients.iter().map(|&c| i128::from(c)).collect();
+            (coefficients, i128::from(*value))
+        })
+        .collect();
+    let mut pivots = Vec::new();
+    for column in 0..program.variables {
+        let rank = pivots.len();
+        let Some(found) = (rank..rows.len()).find(|&row| rows[row].0[column] != 0) else {
+            continue;
+        };
+        rows.swap(rank, found);
+        let (pivot, value) = rows[rank].clone();
+        for (row, (coefficients, row_value)) in rows.iter_mut().enumerate() {
+            let factor = coefficients[column];
+            if row == rank || factor == 0 {
+                continue;
+            }
+            for (coefficient, &p) in coefficients.iter_mut().zip(&pivot) {
+                *coefficient = *coefficient * pivot[column] - factor * p;
+            }
+            *row_value = *row_value * pivot[column] - factor * value;
+            reduce(coefficients, row_value);
+        }
+        pivots.push(column);
+    }
+    // Whatever is left below the pivots reads `0 = value`.
+    if rows[pivots.len()..].iter().any(|&(_, value)| value != 0) {
+        return None;
+    }
+
+    let free: Vec<usize> = (0..program.variables)
+        .filter(|column| !pivots.contains(column))
+        .collect();
+    let rows = pivots
+        .iter()
+        .zip(&rows)
+        .map(|(&variable, (coefficients, value))| Row {
+            variable,
+            pivot: coefficients[variable],
+            free: free.iter().map(|&column| coefficients[column]).collect(),
+            value: *value,
+        })
+        .collect();
+    Some((rows, free))
+}
+
+/// The state of the search over free variables in [`optimise`].
+struct Search<'a> {
+    program: &'a Program,
+    rows: Vec<Row>,
+    free: Vec<usize>,
+    /// The rows that can be solved, and the inequalities that can be
+    /// checked, once the first `d` free variables are set.
+    ready_rows: Vec<Vec<usize>>,
+    ready_inequalities: Vec<Vec<usize>>,
+    /// The smallest each variable could add to the objective.
+    optimistic: Vec<i128>,
+    values: Vec<i64>,
+    best: Option<(i128, Vec<i64>)>,
+}
+
+impl Search<'_> {
+    /// Set `variable`, updating the lower bound on the objective, `None` if
+    /// the value is out of bounds.
+    fn set(&mut self, variable: usize, value: i64, bound: i128) -> Option<i128> {
+        let (lower, upper) = self.program.bounds[variable];
+        if !(lower..=upper).contains(&value) {
+            return None;
+        }
+        self.values[variable] = value;
+        Some(
+            bound + i128::from(self.program.objective[variable]) * i128::from(value)
+                - self.optimistic[variable],
+        )
+    }
+
+    fn run(&mut self, depth: usize, bound: i128, free_values: &mut Vec<i64>) {
+        let mut bound = bound;
+        for idx in 0..self.ready_rows[depth].len() {
+            let row = &self.rows[self.ready_rows[depth][idx]];
+            let variable = row.variable;
+            let Some(value) = row.solve(free_values) else {
+                return;
+            };
+            let Some(next) = self.set(variable, value, bound) else {
+                return;
+            };
+            bound = next;
+        }
+        for &inequality in &self.ready_inequalities[depth] {
+            let (coefficients, value) = &self.program.inequalities[inequality];
+            let total: i128 = coefficients
+                .iter()
+                .zip(&self.values)
+                .map(|(&c, &x)| i128::from(c) * i128::from(x))
+                .sum();
+            if total > i128::from(*value) {
+                return;
+            }
+        }
+        if self.best.as_ref().is_some_and(|&(best, _)| bound >= best) {
+            return;
+        }
+        if depth == self.free.len() {
+            self.best = Some((bound, self.values.clone()));
+            return;
+        }
+
+        let variable = self.free[depth];
+        let (lower, upper) = self.program.bounds[variable];
+        // Try the values that add least to the objective first, so that good
+        // solutions are found early and prune more.
+        let values: Box<dyn Iterator<Item = i64>> = if self.program.objective[variable] >= 0 {
+            Box::new(lower..=upper)
+        } else {
+            Box::new((lower..=upper).rev())
+        };
+        for value in values {
+            let next = self
+                .set(variable, value, bound)
+                .expect("free variables stay in bounds");
+            free_values.push(value);
+            self.run(depth + 1, next, free_values);
+            free_values.pop();
+        }
+    }
+}
+
+/// The solution of `program` with the smallest objective, `None` if there is
+/// none.
+///
+/// The equalities are eliminated, and every value of the remaining free
+/// variables is tried in turn, abandoning any choice as soon as a pivot
+/// variable leaves its bounds, an inequality fails or the objective can't
+/// beat the best solution so far.
+pub(crate) fn optimise(program: &Program) -> Option<Vec<i64>> {
+    if program.bounds.iter().any(|&(lower, upper)| lower > upper) {
+        return None;
+    }
+    let (rows, free) = eliminate(program)?;
+
+    // The depth at which each variable is known, and so the depth at which
+    // each row and inequality can first be used.
+    let mut known = vec![0; program.variables];
+    for (position, &variable) in free.iter().enumerate() {
+        known[variable] = position + 1;
+    }
+    let mut ready_rows = vec![Vec::new(); free.len() + 1];
+    for (idx, row) in rows.iter().enumerate() {
+        let depth = row
+            .free
+            .iter()
+            .rposition(|&coefficient| coefficient != 0)
+            .map_or(0, |last| last + 1);
+        known[row.variable] = depth;
+        ready_rows[depth].push(idx);
+    }
+    let mut ready_inequalities = vec![Vec::new(); free.len() + 1];
+    for (idx, (coefficients, _)) in program.inequalities.iter().enumerate() {
+        let depth = (0..program.variables)
+            .filter(|&variable| coefficients[variable] != 0)
+            .map(|variable| known[variable])
+            .max()
+            .unwrap_or(0);
+        ready_inequalities[depth].push(idx);
+    }
+
+    let optimistic: Vec<i128> = program
+        .objective
+        .iter()
+        .zip(&program.bounds)
+        .map(|(&c, &(lower, upper))| {
+            let c = i128::from(c);
+            (c * i128::from(lower)).min(c * i128::from(upper))
+        })
+        .collect();
+    let bound = optimistic.iter().sum();
+    let mut search = Search {
+        program,
+        rows,
+        free,
+        ready_rows,
+        ready_inequalities,
+        optimistic,
+        values: program.bounds.iter().map(|&(lower, _)| lower).collect(),
+        best: None,
+    };
+    search.run(0, bound, &mut Vec::new());
+    search.best.map(|(_, values)| values)
+}
+
+/// Divide a row through by the greatest common divisor of its entries.
+fn reduce(coefficients: &mut [i128], value: &mut i128) {
+    let divisor = coefficients
+        .iter()
+        .chain(std::iter::once(&*value))
+        .fold(0, |divisor, &entry| gcd(divisor, entry.unsigned_abs()));
+    if divisor > 1 {
+        let divisor = divisor as i128;
+        for coefficient in coefficients.iter_mut() {
+            *coefficient /= divisor;
+        }
+        *value /= divisor;
+    }
+}
+
+fn gcd(a: u128, b: u128) -> u128 {
+    if b == 0 { a } else { gcd(b, a % b) }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Every point of the box `bounds`, by counting through it.
+    fn points(bounds: &[(i64, i64)]) -> Vec<Vec<i64>> {
+        let mut points = vec![Vec::new()];
+        for &(lower, upper) in bounds {
+            points = points
+                .into_iter()
+                .flat_map(|point| {
+                    (lower..=upper).map(move |value| {
+                        let mut point = point.clone();
+                        point.push(value);
+                        point
+                    })
+                })
+                .collect();
+        }
+        points
+    }
+
+    fn dot(coefficients: &[i64], x: &[i64]) -> i64 {
+        coefficients.iter().zip(x).map(|(c, x)| c * x).sum()
+    }
+
+    /// The smallest objective of any point satisfying `program`.
+    fn brute_force(program: &Program) -> Option<i64> {
+        points(&program.bounds)
+            .into_iter()
+            .filter(|x| program.equalities.iter().all(|(a, b)| dot(a, x) == *b))
+            .filter(|x| program.inequalities.iter().all(|(a, b)| dot(a, x) <= *b))
+            .map(|x| dot(&program.objective, &x))
+            .min()
+    }
+
+    #[test]
+    fn fewest_presses_for_a_machine() {
+        // Buttons (3) (1,3) (2) (2,3) (0,2) (0,1) and joltages {3,5,4,7}.
+        let buttons: [&[usize]; 6] = [&[3], &[1, 3], &[2], &[2, 3], &[0, 2], &[0, 1]];
+        let mut system = IntegerSystem::new(buttons.len());
+        for (counter, joltage) in [3, 5, 4, 7].into_iter().enumerate() {
+            let coefficients = buttons
+                .iter()
+                .map(|button| u64::from(button.contains(&counter)))
+                .collect();
+            system.equation(coefficients, joltage);
+        }
+        let presses = system.minimise_sum().unwrap();
+        assert_eq!(presses.iter().sum::<u64>(), 10);
+    }
+
+    #[test]
+    fn infeasible_systems() {
+        let mut odd = IntegerSystem::new(1);
+        odd.equation(vec![2], 3);
+        assert_eq!(odd.minimise_sum(), Err(Infeasible));
+
+        let mut contradictory = IntegerSystem::new(2);
+        contradictory.equation(vec![1, 1], 1);
+        contradictory.equation(vec![1, 1], 2);
+        assert_eq!(contradictory.minimise_sum(), Err(Infeasible));
+
+        // Only solvable with a negative variable.
+        let mut negative = IntegerSystem::new(2);
+        negative.equation(vec![1, 1], 1);
+        negative.equation(vec![1, 0], 2);
+        assert_eq!(negative.minimise_sum(), Err(Infeasible));
+    }
+
+    #[test]
+    fn trivial_systems() {
+        assert_eq!(IntegerSystem::new(0).minimise_sum(), Ok(vec![]));
+        assert_eq!(IntegerSystem::new(3).minimise_sum(), Ok(vec![0, 0, 0]));
+        let mut zero = IntegerSystem::new(2);
+        zero.equation(vec![0, 0], 0);
+        assert_eq!(zero.minimise_sum(), Ok(vec![0, 0]));
+        zero.equation(vec![0, 0], 1);
+        assert_eq!(zero.minimise_sum(), Err(Infeasible));
+    }
+
+    #[test]
+    fn empty_bounds_are_infeasible() {
+        let program = Program {
+            variables: 1,
+            equalities: Vec::new(),
+            inequalities: Vec::new(),
+            bounds: vec![(1, 0)],
+            objective: vec![1],
+        };
+        assert_eq!(optimise(&program), None);
+    }
+
+    /// A small linear congruential generator, for repeatable programs.
+    struct Lcg(u64);
+
+    impl Lcg {
+        fn below(&mut self, bound: u64) -> i64 {
+            self.0 = self
+                .0
+                .wrapping_mul(6_364_136_223_846_793_005)
+                .wrapping_add(1);
+            ((self.0 >> 33) % bound) as i64
+        }
+
+        /// `variables` coefficients from `-shift` to `4 - shift`.
+        fn row(&mut self, variables: usize, shift: i64) -> Vec<i64> {
+            (0..variables).map(|_| self.below(5) - shift).collect()
+        }
+    }
+
+    #[test]
+    fn programs_match_brute_force() {
+        let mut rng = Lcg(99);
+        for _ in 0..200 {
+            let variables = 1 + rng.below(4) as usize;
+            let mut equalities = Vec::new();
+            for _ in 0..rng.below(3) {
+                equalities.push((rng.row(variables, 1), rng.below(7) - 2));
+            }
+            let mut inequalities = Vec::new();
+            for _ in 0..rng.below(3) {
+                inequalities.push((rng.row(variables, 2), rng.below(9) - 3));
+            }
+            let objective = rng.row(variables, 2);
+            let mut bounds = Vec::new();
+            for _ in 0..variables {
+                let lower = rng.below(5) - 2;
+                bounds.push((lower, lower + rng.below(4)));
+            }
+            let program = Program {
+                variables,
+                equalities,
+                inequalities,
+                bounds,
+                objective,
+            };
+
+            let found = optimise(&program);
+            let objective = found.as_ref().map(|x| dot(&program.objective, x));
+            assert_eq!(objective, brute_force(&program), "{:?}", program);
+            if let Some(x) = found {
+                assert!(program.equalities.iter().all(|(a, b)| dot(a, &x) == *b));
+                assert!(program.inequalities.iter().all(|(a, b)| dot(a, &x) <= *b));
+                let within =
+                    |(x, (lower, upper)): (&i64, &(i64, i64))| (lower..=upper).contains(&x);
+                assert!(x.iter().zip(&program.bounds).all(within));
+            }
+        }
+    }
+}
@@ -0,0 +1,429 @@
+pub mod mapping;
+
+use std::fmt::Debug;
+use std::iter;
+
+/// A primitive integer type usable as an interval bound.
+pub trait Integer: Copy + Ord + Debug {
+    /// The next integer, `None` at the maximum.
+    fn succ(self) -> Option<Self>;
+
+    /// The previous integer, `None` at the minimum.
+    fn pred(self) -> Option<Self>;
+
+    /// `hi - lo` for `lo <= hi`, which always fits in a `u128`.
+    fn distance(lo: Self, hi: Self) -> u128;
+
+    /// The integer `distance` above this one, `None` if it's out of range.
+    fn add_distance(self, distance: u128) -> Option<Self>;
+}
+
+macro_rules! integer {
+    ($($t:ty => $unsigned:ty),*) => {
+        $(impl Integer for $t {
+            fn succ(self) -> Option<Self> {
+                self.checked_add(1)
+            }
+
+            fn pred(self) -> Option<Self> {
+                self.checked_sub(1)
+            }
+
+            fn distance(lo: Self, hi: Self) -> u128 {
+                (hi as $unsigned).wrapping_sub(lo as $unsigned) as u128
+            }
+
+            fn add_distance(self, distance: u128) -> Option<Self> {
+                (distance <= Self::distance(self, <$t>::MAX))
+                    .then(|| (self as $unsigned).wrapping_add(distance as $unsigned) as $t)
+            }
+        })*
+    };
+}
+
+integer!(
+    i8 => u8, i16 => u16, i32 => u32, i64 => u64, i128 => u128, isize => usize,
+    u8 => u8, u16 => u16, u32 => u32, u64 => u64, u128 => u128, usize => usize
+);
+
+/// A non-empty range of integers, including both `start` and `end`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Interval<T> {
+    pub start: T,
+    pub end: T,
+}
+
+impl<T: Integer> Interval<T> {
+    /// The integers from `start` to `end` inclusive, `None` if `end < start`.
+    pub fn closed(start: T, end: T) -> Option<Self> {
+        (start <= end).then_some(Interval { start, end })
+    }
+
+    /// The integers from `start` up to but excluding `end`, `None` if
+    /// `end <= start`.
+    pub fn half_open(start: T, end: T) -> Option<Self> {
+        Interval::closed(start, end.pred()?)
+    }
+
+    pub fn point(value: T) -> Self {
+        Interval {
+            start: value,
+            end: value,
+        }
+    }
+
+    /// The exclusive end of the interval, `None` if it ends at `T`'s maximum.
+    pub fn end_exclusive(self) -> Option<T> {
+        self.end.succ()
+    }
+
+    /// Number of integers in the interval, saturating for the whole of `u128`
+    /// or `i128`.
+    pub fn count(self) -> u128 {
+        T::distance(self.start, self.end).saturating_add(1)
+    }
+
+    pub fn contains(self, value: T) -> bool {
+        self.start <= value && value <= self.end
+    }
+
+    /// Whether the intervals share at least one integer.
+    pub fn overlaps(self, other: Self) -> bool {
+        self.start <= other.end && other.start <= self.end
+    }
+
+    /// Whether the intervals overlap or one starts right after the other ends,
+    /// so that their union is a single interval.
+    pub fn touches(self, other: Self) -> bool {
+        let before = |a: Self, b: Self| a.end.succ().is_some_and(|next| next < b.start);
+        !before(self, other) && !before(other, self)
+    }
+
+    pub fn intersection(self, other: Self) -> Option<Self> {
+        Interval::closed(self.start.max(other.start), self.end.min(other.end))
+    }
+
+    /// The single interval covering both, if they touch.
+    pub fn merge(self, other: Self) -> Option<Self> {
+        self.touches(other).then(|| Interval {
+            start: self.start.min(other.start),
+            end: self.end.max(other.end),
+        })
+    }
+}
+
+/// A set of integers stored as sorted, disjoint and non-adjacent intervals.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct IntervalSet<T> {
+    intervals: Vec<Interval<T>>,
+}
+
+impl<T> Default for IntervalSet<T> {
+    fn default() -> Self {
+        IntervalSet {
+            intervals: Vec::new(),
+        }
+    }
+}
+
+impl<T: Integer> IntervalSet<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.intervals.is_empty()
+    }
+
+    /// The normalised intervals, in order.
+    pub fn intervals(&self) -> &[Interval<T>] {
+        &self.intervals
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = Interval<T>> + '_ {
+        self.intervals.iter().copied()
+    }
+
+    /// Total number of integers in the set, saturating at `u128::MAX`.
+    pub fn covered_count(&self) -> u128 {
+        self.iter().fold(0, |total: u128, interval| {
+            total.saturating_add(interval.count())
+        })
+    }
+
+    /// The interval of the set containing `value`, found by binary search.
+    pub fn interval_containing(&self, value: T) -> Option<Interval<T>> {
+        let idx = self.intervals.partition_point(|i| i.end < value);
+        self.intervals
+            .get(idx)
+            .copied()
+            .filter(|i| i.start <= value)
+    }
+
+    pub fn contains(&self, value: T) -> bool {
+        self.interval_containing(value).is_some()
+    }
+
+    /// Add every integer of `interval`, merging it with any intervals it
+    /// overlaps or is adjacent to.
+    pub fn insert(&mut self, interval: Interval<T>) {
+        let first = self
+            .intervals
+            .partition_point(|i| i.end.succ().is_some_and(|next| next < interval.start));
+        let last = self
+            .intervals
+            .partition_point(|i| interval.end.succ().is_none_or(|next| i.start <= next));
+        let merged = self.intervals[first..last]
+            .iter()
+            .fold(interval, |merged, &i| Interval {
+                start: merged.start.min(i.start),
+                end: merged.end.max(i.end),
+            });
+        self.intervals.splice(first..last, iter::once(merged));
+    }
+
+    /// Remove every integer of `interval`, splitting intervals it cuts.
+    pub fn remove(&mut self, interval: Interval<T>) {
+        *self = self.difference(&IntervalSet::from(interval));
+    }
+
+    /// Integers in either set.
+    pub fn union(&self, other: &Self) -> Self {
+        self.iter().chain(other.iter()).collect()
+    }
+
+    /// Integers in both sets.
+    pub fn intersection(&self, other: &Self) -> Self {
+        let (mut a, mut b) = (
+            self.intervals.iter().peekable(),
+            other.intervals.iter().peekable(),
+        );
+        let mut intervals = Vec::new();
+        while let (Some(&&x), Some(&&y)) = (a.peek(), b.peek()) {
+            intervals.extend(x.intersection(y));
+            if x.end < y.end {
+                a.next();
+            } else {
+                b.next();
+            }
+        }
+        // Both sets have a gap between neighbouring intervals, so pieces of
+        // them can't touch either.
+        IntervalSet { intervals }
+    }
+
+    /// Integers in this set but not in `other`.
+    pub fn difference(&self, other: &Self) -> Self {
+        let mut intervals = Vec::new();
+        for interval in self.iter() {
+            let first = other.intervals.partition_point(|o| o.end < interval.start);
+            let mut rest = Some(interval.start);
+            for cut in other.intervals[first..]
+                .iter()
+                .take_while(|o| o.start <= interval.end)
+            {
+                let Some(start) = rest else { break };
+                if let Some(end) = cut.start.pred() {
+                    intervals.extend(Interval::closed(start, end));
+                }
+                rest = cut.end.succ();
+            }
+            if let Some(start) = rest {
+                intervals.extend(Interval::closed(start, interval.end));
+            }
+        }
+        IntervalSet { intervals }
+    }
+}
+
+impl<T: Integer> From<Interval<T>> for IntervalSet<T> {
+    fn from(interval: Interval<T>) -> Self {
+        IntervalSet {
+            intervals: vec![interval],
+        }
+    }
+}
+
+impl<T: Integer> FromIterator<Interval<T>> for IntervalSet<T> {
+    fn from_iter<I: IntoIterator<Item = Interval<T>>>(iter: I) -> Self {
+        let mut sorted: Vec<Interval<T>> = iter.into_iter().collect();
+        sorted.sort_unstable();
+        let mut intervals: Vec<Interval<T>> = Vec::with_capacity(sorted.len());
+        for interval in sorted {
+            match intervals.last_mut() {
+                Some(last) if last.touches(interval) => last.end = last.end.max(interval.end),
+                _ => intervals.push(interval),
+            }
+        }
+        IntervalSet { intervals }
+    }
+}
+
+impl<T: Integer> Extend<Interval<T>> for IntervalSet<T> {
+    fn extend<I: IntoIterator<Item = Interval<T>>>(&mut self, iter: I) {
+        for interval in iter {
+            self.insert(interval);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn set(intervals: &[(i32, i32)]) -> IntervalSet<i32> {
+        intervals
+            .iter()
+            .map(|&(start, end)| Interval::closed(start, end).unwrap())
+            .collect()
+    }
+
+    fn pairs(set: &IntervalSet<i32>) -> Vec<(i32, i32)> {
+        set.iter().map(|i| (i.start, i.end)).collect()
+    }
+
+    #[test]
+    fn intervals_need_an_integer_in_them() {
+        assert_eq!(Interval::closed(3, 3), Some(Interval::point(3)));
+        assert_eq!(Interval::closed(4, 3), None);
+        assert_eq!(Interval::half_open(3, 4), Some(Interval::point(3)));
+        assert_eq!(Interval::half_open(3, 3), None);
+        assert_eq!(Interval::half_open(u8::MIN, u8::MIN), None);
+    }
+
+    #[test]
+    fn counts_reach_the_ends_of_the_type() {
+        assert_eq!(Interval::point(7).count(), 1);
+        assert_eq!(Interval::closed(-128i8, 127).unwrap().count(), 256);
+        assert_eq!(
+            Interval::closed(i64::MIN, i64::MAX).unwrap().count(),
+            1 << 64
+        );
+        assert_eq!(Interval::closed(0, u128::MAX).unwrap().count(), u128::MAX);
+        assert_eq!(Interval::point(u8::MAX).end_exclusive(), None);
+        assert_eq!(Interval::point(254u8).end_exclusive(), Some(255));
+    }
+
+    #[test]
+    fn touching_includes_adjacent_intervals() {
+        let a = Interval::closed(1, 3).unwrap();
+        assert!(a.touches(Interval::closed(4, 6).unwrap()));
+        assert!(!a.overlaps(Interval::closed(4, 6).unwrap()));
+        assert!(!a.touches(Interval::closed(5, 6).unwrap()));
+        assert_eq!(
+            a.merge(Interval::closed(4, 6).unwrap()),
+            Interval::closed(1, 6)
+        );
+        assert_eq!(a.merge(Interval::closed(5, 6).unwrap()), None);
+        assert_eq!(
+            a.intersection(Interval::closed(3, 9).unwrap()),
+            Some(Interval::point(3))
+        );
+        assert_eq!(a.intersection(Interval::closed(4, 9).unwrap()), None);
+        let top = Interval::closed(250u8, 255).unwrap();
+        assert!(top.touches(Interval::point(249)));
+        assert!(top.touches(Interval::point(255)));
+    }
+
+    #[test]
+    fn sets_merge_overlapping_and_adjacent_intervals() {
+        let merged = set(&[(10, 12), (1, 3), (4, 5), (2, 2), (14, 20)]);
+        assert_eq!(pairs(&merged), [(1, 5), (10, 12), (14, 20)]);
+        assert_eq!(merged.covered_count(), 5 + 3 + 7);
+
+        let mut inserted = merged.clone();
+        inserted.insert(Interval::point(13));
+        assert_eq!(pairs(&inserted), [(1, 5), (10, 20)]);
+        inserted.insert(Interval::closed(0, 30).unwrap());
+        assert_eq!(pairs(&inserted), [(0, 30)]);
+    }
+
+    #[test]
+    fn membership_is_found_by_interval() {
+        let s = set(&[(1, 5), (10, 12)]);
+        assert_eq!(s.interval_containing(11), Interval::closed(10, 12));
+        assert!(s.contains(1) && s.contains(5) && s.contains(12));
+        assert!(!s.contains(0) && !s.contains(6) && !s.contains(13));
+    }
+
+    #[test]
+    fn set_operations() {
+        let a = set(&[(1, 5), (10, 20)]);
+        let b = set(&[(4, 11), (15, 15), (30, 31)]);
+        assert_eq!(pairs(&a.union(&b)), [(1, 20), (30, 31)]);
+        assert_eq!(pairs(&a.intersection(&b)), [(4, 5), (10, 11), (15, 15)]);
+        assert_eq!(pairs(&a.difference(&b)), [(1, 3), (12, 14), (16, 20)]);
+        assert_eq!(pairs(&b.difference(&a)), [(6, 9), (30, 31)]);
+
+        let mut removed = a.clone();
+        removed.remove(Interval::closed(2, 12).unwrap());
+        assert_eq!(pairs(&removed), [(1, 1), (13, 20)]);
+    }
+
+    #[test]
+    fn empty_sets() {
+        let empty = IntervalSet::<i32>::new();
+        let a = set(&[(1, 5)]);
+        assert!(empty.is_empty());
+        assert_eq!(empty.covered_count(), 0);
+        assert!(!empty.contains(0));
+        assert_eq!(empty.union(&a), a);
+        assert!(empty.intersection(&a).is_empty());
+        assert!(a.intersection(&empty).is_empty());
+        assert_eq!(a.difference(&empty), a);
+        assert!(a.difference(&a).is_empty());
+        assert_eq!(IntervalSet::from_iter(iter::empty()), empty);
+    }
+
+    #[test]
+    fn sets_reach_the_ends_of_the_type() {
+        let mut s: IntervalSet<u8> = IntervalSet::from(Interval::closed(0, 255).unwrap());
+        assert_eq!(s.covered_count(), 256);
+        s.remove(Interval::closed(0, 9).unwrap());
+        s.remove(Interval::point(255));
+        assert_eq!(s.intervals(), [Interval::closed(10, 254).unwrap()]);
+        s.insert(Interval::point(255));
+        s.insert(Interval::point(0));
+        assert_eq!(
+            s.intervals(),
+            [Interval::point(0), Interval::closed(10, 255).unwrap()]
+        );
+
+        let whole = IntervalSet::from(Interval::closed(i128::MIN, i128::MAX).unwrap());
+        assert_eq!(whole.covered_count(), u128::MAX);
+        let halves = IntervalSet::from_iter([
+            Interval::closed(i128::MIN, -1).unwrap(),
+            Interval::closed(0, i128::MAX).unwrap(),
+        ]);
+        assert_eq!(halves, whole);
+    }
+
+    #[test]
+    fn set_operations_match_sets_of_values() {
+        // Every pair of sets built from a few intervals of 0..16.
+        let sets: Vec<IntervalSet<u8>> = (0u32..1 << 16)
+            .step_by(997)
+            .map(|bits| {
+                (0..16u8)
+                    .filter(|&i| bits & (1 << i) != 0)
+                    .map(Interval::point)
+                    .collect()
+            })
+            .collect();
+        let values = |s: &IntervalSet<u8>| (0..16u8).filter(|&v| s.contains(v)).collect::<Vec<_>>();
+        for a in &sets {
+            for b in &sets {
+                let (va, vb) = (values(a), values(b));
+                let union: Vec<u8> = (0..16)
+                    .filter(|v| va.contains(v) || vb.contains(v))
+                    .collect();
+                let both: Vec<u8> = va.iter().copied().filter(|v| vb.contains(v)).collect();
+                let only: Vec<u8> = va.iter().copied().filter(|v| !vb.contains(v)).collect();
+                assert_eq!(values(&a.union(b)), union);
+                assert_eq!(values(&a.intersection(b)), both);
+                assert_eq!(values(&a.difference(b)), only);
+                assert_eq!(a.difference(b).covered_count(), only.len() as u128);
+            }
+        }
+    }
+}
@@ -0,0 +1,275 @@
+use super::{Integer, Interval, IntervalSet};
+
+/// Moves every integer of `source` by the same amount, so that `source.start`
+/// lands on `destination`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Rule<T> {
+    source: Interval<T>,
+    destination: T,
+}
+
+impl<T: Integer> Rule<T> {
+    /// `None` if the moved interval would run past `T`'s maximum.
+    pub fn new(source: Interval<T>, destination: T) -> Option<Self> {
+        destination.add_distance(T::distance(source.start, source.end))?;
+        Some(Rule {
+            source,
+            destination,
+        })
+    }
+
+    pub fn source(self) -> Interval<T> {
+        self.source
+    }
+
+    /// Where the whole source interval ends up.
+    pub fn image(self) -> Interval<T> {
+        self.map_interval(self.source)
+    }
+
+    /// Where `value`, which must be in the source interval, ends up.
+    fn map(self, value: T) -> T {
+        self.destination
+            .add_distance(T::distance(self.source.start, value))
+            .expect("rules are checked to fit when they're made")
+    }
+
+    fn map_interval(self, interval: Interval<T>) -> Interval<T> {
+        Interval {
+            start: self.map(interval.start),
+            end: self.map(interval.end),
+        }
+    }
+
+    /// The same rule applied to only part of its source interval.
+    fn restrict(self, source: Interval<T>) -> Self {
+        Rule {
+            source,
+            destination: self.map(source.start),
+        }
+    }
+
+    /// The part of the source interval that ends up in `image`, which must be
+    /// part of this rule's image.
+    fn preimage(self, image: Interval<T>) -> Interval<T> {
+        let start = self.destination;
+        let from = |value| {
+            self.source
+                .start
+                .add_distance(T::distance(start, value))
+                .expect("part of the image has a preimage")
+        };
+        Interval {
+            start: from(image.start),
+            end: from(image.end),
+        }
+    }
+}
+
+/// A piecewise offset map: integers covered by a rule are moved by it, and
+/// everything else maps to itself. Works on whole intervals at a time, never
+/// on individual values.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Mapping<T> {
+    /// Disjoint rules that actually move something, sorted by source.
+    rules: Vec<Rule<T>>,
+    domain: IntervalSet<T>,
+}
+
+impl<T: Integer> Mapping<T> {
+    /// Build a mapping from rules that may overlap, in which case the earlier
+    /// rule wins.
+    pub fn new(rules: impl IntoIterator<Item = Rule<T>>) -> Self {
+        let mut mapping = Mapping::identity();
+        for rule in rules {
+            let new = IntervalSet::from(rule.source).difference(&mapping.domain);
+            mapping.rules.extend(
+                new.iter()
+                    .map(|source| rule.restrict(source))
+                    .filter(|rule| rule.source.start != rule.destination),
+            );
+            mapping.domain = mapping.domain.union(&new);
+        }
+        mapping.rules.sort_unstable_by_key(|rule| rule.source);
+        mapping.domain = mapping.rules.iter().map(|rule| rule.source).collect();
+        mapping
+    }
+
+    /// The mapping that leaves everything where it is.
+    pub fn identity() -> Self {
+        Mapping {
+            rules: Vec::new(),
+            domain: IntervalSet::new(),
+        }
+    }
+
+    /// Rules that move something, disjoint and sorted by source.
+    pub fn rules(&self) -> &[Rule<T>] {
+        &self.rules
+    }
+
+    /// Rules overlapping `interval`, each restricted to the overlap.
+    fn rules_within(&self, interval: Interval<T>) -> impl Iterator<Item = Rule<T>> + '_ {
+        let first = self
+            .rules
+            .partition_point(|rule| rule.source.end < interval.start);
+        self.rules[first..]
+            .iter()
+            .take_while(move |rule| rule.source.start <= interval.end)
+            .filter_map(move |&rule| Some(rule.restrict(rule.source.intersection(interval)?)))
+    }
+
+    pub fn map_value(&self, value: T) -> T {
+        self.rules_within(Interval::point(value))
+            .next()
+            .map_or(value, |rule| rule.map(value))
+    }
+
+    /// Map every integer of `set`, splitting its intervals wherever they
+    /// cross a rule boundary.
+    pub fn apply(&self, set: &IntervalSet<T>) -> IntervalSet<T> {
+        let moved = set
+            .iter()
+            .flat_map(|interval| self.rules_within(interval))
+            .map(Rule::image);
+        set.difference(&self.domain).iter().chain(moved).collect()
+    }
+
+    /// The mapping that applies this one and then `next`.
+    pub fn then(&self, next: &Mapping<T>) -> Mapping<T> {
+        let mut rules = Vec::new();
+        for &rule in &self.rules {
+            let image = rule.image();
+            for moved in next.rules_within(image) {
+                let source = rule.preimage(moved.source);
+                rules.extend(Rule::new(source, moved.destination));
+            }
+            for unmoved in IntervalSet::from(image).difference(&next.domain).iter() {
+                let source = rule.preimage(unmoved);
+                rules.extend(Rule::new(source, unmoved.start));
+            }
+        }
+        for untouched in next.domain.difference(&self.domain).iter() {
+            rules.extend(next.rules_within(untouched));
+        }
+        Mapping::new(rules)
+    }
+
+    /// A single mapping equivalent to applying each stage in turn.
+    pub fn compose<'a>(stages: impl IntoIterator<Item = &'a Mapping<T>>) -> Mapping<T>
+    where
+        T: 'a,
+    {
+        stages
+            .into_iter()
+            .fold(Mapping::identity(), |mapping, stage| mapping.then(stage))
+    }
+}
+
+impl<T: Integer> FromIterator<Rule<T>> for Mapping<T> {
+    fn from_iter<I: IntoIterator<Item = Rule<T>>>(iter: I) -> Self {
+        Mapping::new(iter)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rule(start: u8, end: u8, destination: u8) -> Rule<u8> {
+        Rule::new(Interval::closed(start, end).unwrap(), destination).unwrap()
+    }
+
+    /// Map each value of `set` on its own through every stage in turn.
+    fn map_each(stages: &[&Mapping<u8>], set: &IntervalSet<u8>) -> IntervalSet<u8> {
+        (0..=u8::MAX)
+            .filter(|&value| set.contains(value))
+            .map(|value| {
+                let mapped = stages.iter().fold(value, |v, stage| stage.map_value(v));
+                Interval::point(mapped)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn rules_must_fit_in_the_type() {
+        assert!(Rule::new(Interval::closed(0u8, 5).unwrap(), 250).is_some());
+        assert!(Rule::new(Interval::closed(0u8, 5).unwrap(), 251).is_none());
+        assert_eq!(rule(0, 5, 250).image(), Interval::closed(250, 255).unwrap());
+    }
+
+    #[test]
+    fn values_outside_every_rule_stay_put() {
+        let mapping = Mapping::new([rule(10, 19, 100), rule(30, 30, 0)]);
+        assert_eq!(mapping.map_value(9), 9);
+        assert_eq!(mapping.map_value(10), 100);
+        assert_eq!(mapping.map_value(19), 109);
+        assert_eq!(mapping.map_value(20), 20);
+        assert_eq!(mapping.map_value(30), 0);
+        assert_eq!(Mapping::identity().map_value(42), 42);
+    }
+
+    #[test]
+    fn earlier_rules_win_where_they_overlap() {
+        let mapping = Mapping::new([rule(10, 19, 100), rule(15, 24, 200), rule(0, 3, 0)]);
+        assert_eq!(mapping.map_value(15), 105);
+        assert_eq!(mapping.map_value(20), 205);
+        assert_eq!(mapping.map_value(2), 2);
+        // Rules that don't move anything are left out.
+        assert_eq!(mapping.rules().len(), 2);
+    }
+
+    #[test]
+    fn apply_splits_intervals_at_rule_boundaries() {
+        let mapping = Mapping::new([rule(10, 19, 100), rule(20, 24, 0)]);
+        let set = IntervalSet::from(Interval::closed(5, 22).unwrap());
+        let expected: IntervalSet<u8> = [(0, 2), (5, 9), (100, 109)]
+            .into_iter()
+            .map(|(start, end)| Interval::closed(start, end).unwrap())
+            .collect();
+        assert_eq!(mapping.apply(&set), expected);
+        assert_eq!(mapping.apply(&set), map_each(&[&mapping], &set));
+        assert!(mapping.apply(&IntervalSet::new()).is_empty());
+    }
+
+    #[test]
+    fn composing_stages_matches_mapping_each_value() {
+        let first = Mapping::new([rule(0, 9, 246), rule(50, 99, 20), rule(200, 255, 100)]);
+        let second = Mapping::new([rule(240, 255, 0), rule(10, 40, 225), rule(120, 130, 5)]);
+        let third = Mapping::new([rule(0, 15, 60), rule(250, 255, 244)]);
+        let everything = IntervalSet::from(Interval::closed(0, u8::MAX).unwrap());
+
+        for stages in [
+            vec![&first, &second],
+            vec![&second, &first],
+            vec![&first, &second, &third],
+            vec![&third, &first, &second],
+        ] {
+            let composed = Mapping::compose(stages.iter().copied());
+            for value in 0..=u8::MAX {
+                let expected = stages.iter().fold(value, |v, stage| stage.map_value(v));
+                assert_eq!(composed.map_value(value), expected, "{}", value);
+            }
+            assert_eq!(composed.apply(&everything), map_each(&stages, &everything));
+
+            let staged = stages
+                .iter()
+                .fold(everything.clone(), |set, stage| stage.apply(&set));
+            assert_eq!(staged, map_each(&stages, &everything));
+        }
+    }
+
+    #[test]
+    fn composing_with_the_identity_changes_nothing() {
+        let mapping = Mapping::new([rule(0, 9, 246), rule(100, 120, 0)]);
+        assert_eq!(mapping.then(&Mapping::identity()), mapping);
+        assert_eq!(Mapping::identity().then(&mapping), mapping);
+        assert_eq!(Mapping::compose([]), Mapping::<u8>::identity());
+    }
+
+    #[test]
+    fn stages_that_undo_each_other_compose_to_the_identity() {
+        let there = Mapping::new([rule(0, 5, 250), rule(250, 255, 0)]);
+        assert_eq!(there.then(&there), Mapping::identity());
+    }
+}
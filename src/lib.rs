@@ -1,2 +1,18 @@
+pub mod compress;
+pub mod day;
+pub mod digits;
+pub mod disjoint_set;
+pub mod error;
+pub mod exact_cover;
+pub mod gf2;
+pub mod graph;
 pub mod grid;
+pub mod ilp;
+pub mod interval;
+pub mod point;
+pub mod polygon;
+pub mod polyomino;
+pub mod rectangle;
+pub mod solver;
+pub mod spatial;
 pub mod utils;
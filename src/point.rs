@@ -0,0 +1,214 @@
+use crate::error::{Error, Result};
+use crate::grid::GridPosition;
+use crate::interval::Integer;
+use crate::utils;
+use std::fmt::{Display, Formatter};
+use std::ops::{Add, Mul, Sub};
+use std::str::FromStr;
+
+/// A point or vector on a plane. Ordered by `x`, then `y`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Point2<T> {
+    pub x: T,
+    pub y: T,
+}
+
+/// A point or vector in space. Ordered by `x`, then `y`, then `z`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Point3<T> {
+    pub x: T,
+    pub y: T,
+    pub z: T,
+}
+
+impl<T> Point2<T> {
+    pub fn new(x: T, y: T) -> Self {
+        Point2 { x, y }
+    }
+}
+
+impl<T> Point3<T> {
+    pub fn new(x: T, y: T, z: T) -> Self {
+        Point3 { x, y, z }
+    }
+}
+
+/// `|a - b|`, which always fits in a `u128`.
+fn abs_diff<T: Integer>(a: T, b: T) -> u128 {
+    T::distance(a.min(b), a.max(b))
+}
+
+/// The sum of `terms`, `None` if any of them is or the sum overflows.
+fn checked_sum<const N: usize>(terms: [Option<u128>; N]) -> Option<u128> {
+    terms
+        .into_iter()
+        .try_fold(0u128, |total, term| total.checked_add(term?))
+}
+
+/// Split a line like `1,2,3` into exactly `N` comma separated coordinates.
+fn coordinates<'a, const N: usize>(
+    line: &'a str,
+    line_no: usize,
+    expected: &str,
+) -> Result<[&'a str; N]> {
+    let coordinates: Vec<&str> = line.trim().split(',').collect();
+    coordinates
+        .try_into()
+        .map_err(|_| Error::parse(line_no, expected, line))
+}
+
+macro_rules! point {
+    ($name:ident, $n:literal, $($field:ident),+) => {
+        impl<T: Add<Output = T>> Add for $name<T> {
+            type Output = Self;
+
+            fn add(self, other: Self) -> Self {
+                $name { $($field: self.$field + other.$field),+ }
+            }
+        }
+
+        impl<T: Sub<Output = T>> Sub for $name<T> {
+            type Output = Self;
+
+            fn sub(self, other: Self) -> Self {
+                $name { $($field: self.$field - other.$field),+ }
+            }
+        }
+
+        /// Scale by a factor.
+        impl<T: Mul<Output = T> + Copy> Mul<T> for $name<T> {
+            type Output = Self;
+
+            fn mul(self, factor: T) -> Self {
+                $name { $($field: self.$field * factor),+ }
+            }
+        }
+
+        impl<T: Integer> $name<T> {
+            /// Sum of the distances along each axis. Panics if it doesn't fit
+            /// in a `u128`, which can only happen with 128-bit coordinates.
+            pub fn manhattan(self, other: Self) -> u128 {
+                checked_sum([$(Some(abs_diff(self.$field, other.$field))),+])
+                    .expect("manhattan distance overflows a u128")
+            }
+
+            /// Largest of the distances along each axis.
+            pub fn chebyshev(self, other: Self) -> u128 {
+                0 $(.max(abs_diff(self.$field, other.$field)))+
+            }
+
+            /// Square of the Euclidean distance. Panics if it doesn't fit in a
+            /// `u128`, which never happens while the points are less than
+            /// 2^63 apart along every axis, so never with 32-bit coordinates.
+            pub fn squared_distance(self, other: Self) -> u128 {
+                checked_sum([$(abs_diff(self.$field, other.$field).checked_pow(2)),+])
+                    .expect("squared distance overflows a u128")
+            }
+        }
+
+        impl<T: FromStr> $name<T> {
+            /// Parse comma separated coordinates like `1,2` from line `line_no`
+            /// of the input, describing what was `expected` if they're invalid.
+            pub fn parse(line: &str, line_no: usize, expected: &str) -> Result<Self> {
+                let [$($field),+] = coordinates::<$n>(line, line_no, expected)?;
+                Ok($name {
+                    $($field: utils::parse_value($field, line_no, expected)?),+
+                })
+            }
+        }
+
+        impl<T> From<[T; $n]> for $name<T> {
+            fn from([$($field),+]: [T; $n]) -> Self {
+                $name { $($field),+ }
+            }
+        }
+
+        impl<T> From<$name<T>> for [T; $n] {
+            fn from(point: $name<T>) -> Self {
+                [$(point.$field),+]
+            }
+        }
+    };
+}
+
+point!(Point2, 2, x, y);
+point!(Point3, 3, x, y, z);
+
+impl<T: Display> Display for Point2<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{},{}", self.x, self.y)
+    }
+}
+
+impl<T: Display> Display for Point3<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{},{},{}", self.x, self.y, self.z)
+    }
+}
+
+impl<T: Copy + TryFrom<usize>> Point2<T> {
+    /// The point at a grid position, with `x` the column and `y` the row.
+    /// `None` if the position doesn't fit in `T`.
+    pub fn from_grid_position(position: GridPosition) -> Option<Self> {
+        Some(Point2 {
+            x: T::try_from(position.1).ok()?,
+            y: T::try_from(position.0).ok()?,
+        })
+    }
+}
+
+impl<T: Copy> Point2<T>
+where
+    usize: TryFrom<T>,
+{
+    /// The grid position of this point, with `x` the column and `y` the row.
+    /// `None` if either coordinate is negative or too large for a `usize`.
+    pub fn to_grid_position(self) -> Option<GridPosition> {
+        Some(GridPosition(
+            usize::try_from(self.y).ok()?,
+            usize::try_from(self.x).ok()?,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn distances() {
+        let (a, b) = (Point3::new(1, -2, 3), Point3::new(4, 2, 3));
+        assert_eq!(a.manhattan(b), 7);
+        assert_eq!(a.chebyshev(b), 4);
+        assert_eq!(a.squared_distance(b), 25);
+        assert_eq!(a.squared_distance(a), 0);
+    }
+
+    #[test]
+    fn distances_across_the_whole_type() {
+        let (a, b) = (
+            Point2::new(i64::MIN, i64::MIN),
+            Point2::new(i64::MAX, i64::MAX),
+        );
+        assert_eq!(a.manhattan(b), 2 * u128::from(u64::MAX));
+        assert_eq!(a.chebyshev(b), u128::from(u64::MAX));
+        let near = Point3::new(0, 0, 0);
+        let far = Point3::new(1 << 62, -(1 << 62), i64::MAX);
+        assert_eq!(
+            near.squared_distance(far),
+            2 * (1 << 124) + (u128::from(u64::MAX >> 1)).pow(2)
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "squared distance overflows")]
+    fn squared_distance_panics_past_u128() {
+        let _ = Point2::new(i64::MIN, i64::MIN).squared_distance(Point2::new(i64::MAX, i64::MAX));
+    }
+
+    #[test]
+    #[should_panic(expected = "manhattan distance overflows")]
+    fn manhattan_panics_past_u128() {
+        let _ = Point2::new(0u128, 0).manhattan(Point2::new(u128::MAX, 1));
+    }
+}
@@ -0,0 +1,386 @@
+use crate::compress::CompressedPlane;
+use crate::grid::{Grid, GridPosition};
+use crate::point::Point2;
+use std::fmt::{Display, Formatter};
+
+/// Where a point is relative to a polygon.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Location {
+    Inside,
+    Boundary,
+    Outside,
+}
+
+/// The edge from `vertex` to the next vertex isn't horizontal or vertical,
+/// or has zero length.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NotRectilinear {
+    pub vertex: usize,
+}
+
+impl Display for NotRectilinear {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "the edge from vertex {} isn't a horizontal or vertical line",
+            self.vertex
+        )
+    }
+}
+
+/// A simple polygon on integer coordinates whose edges are all horizontal or
+/// vertical, closed by an edge from the last vertex back to the first.
+///
+/// Containment is about the closed polygon, boundary included.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RectilinearPolygon {
+    vertices: Vec<Point2<i64>>,
+}
+
+/// A horizontal or vertical edge, with its ends sorted.
+#[derive(Debug, Clone, Copy)]
+enum Edge {
+    Horizontal { y: i64, x1: i64, x2: i64 },
+    Vertical { x: i64, y1: i64, y2: i64 },
+}
+
+impl RectilinearPolygon {
+    /// Checks that every edge is horizontal or vertical, but not that the
+    /// polygon is simple.
+    pub fn new(vertices: Vec<Point2<i64>>) -> Result<Self, NotRectilinear> {
+        let polygon = RectilinearPolygon { vertices };
+        for (vertex, (a, b)) in polygon.segments().enumerate() {
+            if (a.x == b.x) == (a.y == b.y) {
+                return Err(NotRectilinear { vertex });
+            }
+        }
+        Ok(polygon)
+    }
+
+    pub fn vertices(&self) -> &[Point2<i64>] {
+        &self.vertices
+    }
+
+    fn segments(&self) -> impl Iterator<Item = (Point2<i64>, Point2<i64>)> + '_ {
+        let next = self.vertices.iter().cycle().skip(1);
+        self.vertices.iter().copied().zip(next.copied())
+    }
+
+    fn edges(&self) -> impl Iterator<Item = Edge> + '_ {
+        self.segments().map(|(a, b)| {
+            if a.y == b.y {
+                Edge::Horizontal {
+                    y: a.y,
+                    x1: a.x.min(b.x),
+                    x2: a.x.max(b.x),
+                }
+            } else {
+                Edge::Vertical {
+                    x: a.x,
+                    y1: a.y.min(b.y),
+                    y2: a.y.max(b.y),
+                }
+            }
+        })
+    }
+
+    /// Area enclosed by the polygon, from the shoelace formula.
+    pub fn area(&self) -> u128 {
+        let twice: i128 = self
+            .segments()
+            .map(|(a, b)| i128::from(a.x) * i128::from(b.y) - i128::from(b.x) * i128::from(a.y))
+            .sum();
+        twice.unsigned_abs() / 2
+    }
+
+    /// Number of integer points on the boundary.
+    pub fn boundary_points(&self) -> u128 {
+        self.segments().map(|(a, b)| a.manhattan(b)).sum()
+    }
+
+    /// Number of integer points strictly inside, from Pick's theorem
+    /// `A = I + B/2 - 1`.
+    pub fn interior_points(&self) -> u128 {
+        (self.area() + 1).saturating_sub(self.boundary_points() / 2)
+    }
+
+    /// Number of integer points inside or on the boundary, e.g. the tiles
+    /// covered when every vertex is the centre of a tile.
+    pub fn lattice_points(&self) -> u128 {
+        self.interior_points() + self.boundary_points()
+    }
+
+    pub fn locate(&self, point: Point2<i64>) -> Location {
+        self.locate_doubled(i128::from(point.x) * 2, i128::from(point.y) * 2)
+    }
+
+    /// Locate the point `(x / 2, y / 2)`, so that points halfway between
+    /// integer coordinates can be checked exactly.
+    fn locate_doubled(&self, x: i128, y: i128) -> Location {
+        let double = |value: i64| 2 * i128::from(value);
+        let mut crossings = 0;
+        for edge in self.edges() {
+            match edge {
+                Edge::Horizontal { y: ey, x1, x2 } => {
+                    if y == double(ey) && (double(x1)..=double(x2)).contains(&x) {
+                        return Location::Boundary;
+                    }
+                }
+                Edge::Vertical { x: ex, y1, y2 } => {
+                    if x == double(ex) && (double(y1)..=double(y2)).contains(&y) {
+                        return Location::Boundary;
+                    }
+                    // Cast a ray towards +x, counting each vertical edge it
+                    // crosses, with the half-open range handling vertices.
+                    if double(ex) > x && (double(y1)..double(y2)).contains(&y) {
+                        crossings += 1;
+                    }
+                }
+            }
+        }
+        if crossings % 2 == 1 {
+            Location::Inside
+        } else {
+            Location::Outside
+        }
+    }
+
+    /// Whether the axis-aligned rectangle with opposite corners `a` and `b`
+    /// lies entirely inside the polygon or on its boundary. Works for concave
+    /// polygons, and for rectangles that are only a line or a point.
+    pub fn contains_rectangle(&self, a: Point2<i64>, b: Point2<i64>) -> bool {
+        let (x1, x2) = (a.x.min(b.x), a.x.max(b.x));
+        let (y1, y2) = (a.y.min(b.y), a.y.max(b.y));
+        if x1 < x2 && y1 < y2 {
+            // An edge through the open rectangle would put part of it
+            // outside. Without one, the open rectangle is either all inside
+            // or all outside, so checking its centre decides.
+            let crosses = self.edges().any(|edge| match edge {
+                Edge::Horizontal {
+                    y,
+                    x1: ex1,
+                    x2: ex2,
+                } => y1 < y && y < y2 && ex1 < x2 && ex2 > x1,
+                Edge::Vertical {
+                    x,
+                    y1: ey1,
+                    y2: ey2,
+                } => x1 < x && x < x2 && ey1 < y2 && ey2 > y1,
+            });
+            let centre = (
+                i128::from(x1) + i128::from(x2),
+                i128::from(y1) + i128::from(y2),
+            );
+            !crosses && self.locate_doubled(centre.0, centre.1) != Location::Outside
+        } else {
+            self.contains_segment(Point2::new(x1, y1), Point2::new(x2, y2))
+        }
+    }
+
+    /// The polygon drawn on a grid over `plane`, compressed around the
+    /// vertices so that every cell is wholly inside, on the boundary or
+    /// outside: `#` on the boundary, `X` inside and `.` outside.
+    pub fn draw(&self, plane: &CompressedPlane) -> Grid {
+        let mut grid = Grid::from((plane.rows(), plane.cols()));
+        for row in 0..plane.rows() {
+            let y = plane.ys.start(row);
+            // Where a ray along the row crosses an edge, as in `locate`.
+            let mut crossings: Vec<i64> = self
+                .edges()
+                .filter_map(|edge| match edge {
+                    Edge::Vertical { x, y1, y2 } if (y1..y2).contains(&y) => Some(x),
+                    _ => None,
+                })
+                .collect();
+            crossings.sort_unstable();
+            for col in 0..plane.cols() {
+                let x = plane.xs.start(col);
+                let beyond = crossings.len() - crossings.partition_point(|&cx| cx <= x);
+                if beyond % 2 == 1 {
+                    grid.update_cell(GridPosition(row, col), 'X');
+                }
+            }
+        }
+        for (a, b) in self.segments() {
+            let (Some(a), Some(b)) = (plane.position(a), plane.position(b)) else {
+                continue;
+            };
+            for row in a.0.min(b.0)..=a.0.max(b.0) {
+                for col in a.1.min(b.1)..=a.1.max(b.1) {
+                    grid.update_cell(GridPosition(row, col), '#');
+                }
+            }
+        }
+        grid
+    }
+
+    /// Whether a horizontal or vertical segment lies inside the polygon or on
+    /// its boundary, by checking every point where it could enter or leave
+    /// the polygon and a point between each of those.
+    fn contains_segment(&self, a: Point2<i64>, b: Point2<i64>) -> bool {
+        let horizontal = a.y == b.y;
+        let (start, line) = along_across(horizontal, a);
+        let (end, _) = along_across(horizontal, b);
+
+        let mut stops = vec![start, end];
+        for (p, q) in self.segments() {
+            let (p_along, p_across) = along_across(horizontal, p);
+            let (q_along, q_across) = along_across(horizontal, q);
+            if p_across == q_across {
+                // Parallel, so only matters if it runs along the line.
+                if p_across == line {
+                    stops.extend([p_along, q_along]);
+                }
+            } else if p_across.min(q_across) <= line && line <= p_across.max(q_across) {
+                stops.push(p_along);
+            }
+        }
+        stops.retain(|&stop| start <= stop && stop <= end);
+        stops.sort_unstable();
+        stops.dedup();
+
+        let locate = |doubled_along: i128| {
+            let doubled_across = 2 * i128::from(line);
+            if horizontal {
+                self.locate_doubled(doubled_along, doubled_across)
+            } else {
+                self.locate_doubled(doubled_across, doubled_along)
+            }
+        };
+        let points = stops.iter().map(|&stop| 2 * i128::from(stop));
+        let between = stops
+            .windows(2)
+            .map(|pair| i128::from(pair[0]) + i128::from(pair[1]));
+        points
+            .chain(between)
+            .all(|doubled| locate(doubled) != Location::Outside)
+    }
+}
+
+/// A point's coordinates along and across a horizontal or vertical line.
+fn along_across(horizontal: bool, point: Point2<i64>) -> (i64, i64) {
+    if horizontal {
+        (point.x, point.y)
+    } else {
+        (point.y, point.x)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn polygon(vertices: &[(i64, i64)]) -> RectilinearPolygon {
+        RectilinearPolygon::new(vertices.iter().map(|&(x, y)| Point2::new(x, y)).collect()).unwrap()
+    }
+
+    fn square() -> RectilinearPolygon {
+        polygon(&[(0, 0), (4, 0), (4, 4), (0, 4)])
+    }
+
+    /// An L with the top right quarter of a 4 by 4 square cut out.
+    fn ell() -> RectilinearPolygon {
+        polygon(&[(0, 0), (2, 0), (2, 2), (4, 2), (4, 4), (0, 4)])
+    }
+
+    #[test]
+    fn edges_must_be_horizontal_or_vertical() {
+        let points = |vertices: &[(i64, i64)]| -> Vec<Point2<i64>> {
+            vertices.iter().map(|&(x, y)| Point2::new(x, y)).collect()
+        };
+        let diagonal = RectilinearPolygon::new(points(&[(0, 0), (4, 0), (4, 4), (1, 3), (0, 4)]));
+        assert_eq!(diagonal, Err(NotRectilinear { vertex: 2 }));
+        let repeated = RectilinearPolygon::new(points(&[(0, 0), (4, 0), (4, 0), (4, 4)]));
+        assert_eq!(repeated, Err(NotRectilinear { vertex: 1 }));
+        let single = RectilinearPolygon::new(points(&[(3, 3)]));
+        assert_eq!(single, Err(NotRectilinear { vertex: 0 }));
+    }
+
+    #[test]
+    fn empty_polygon() {
+        let empty = RectilinearPolygon::new(Vec::new()).unwrap();
+        assert_eq!(empty.area(), 0);
+        assert_eq!(empty.boundary_points(), 0);
+        assert_eq!(empty.locate(Point2::new(0, 0)), Location::Outside);
+    }
+
+    #[test]
+    fn counts_from_picks_theorem() {
+        let square = square();
+        assert_eq!(square.area(), 16);
+        assert_eq!(square.boundary_points(), 16);
+        assert_eq!(square.interior_points(), 9);
+        assert_eq!(square.lattice_points(), 25);
+
+        let ell = ell();
+        assert_eq!(ell.area(), 12);
+        assert_eq!(ell.boundary_points(), 16);
+        assert_eq!(ell.interior_points(), 5);
+        assert_eq!(ell.lattice_points(), 21);
+    }
+
+    #[test]
+    fn counts_match_locating_each_point() {
+        for polygon in [square(), ell()] {
+            let located = |wanted| {
+                (-1..=5)
+                    .flat_map(|x| (-1..=5).map(move |y| Point2::new(x, y)))
+                    .filter(|&point| polygon.locate(point) == wanted)
+                    .count() as u128
+            };
+            assert_eq!(located(Location::Boundary), polygon.boundary_points());
+            assert_eq!(located(Location::Inside), polygon.interior_points());
+        }
+    }
+
+    #[test]
+    fn locating_points() {
+        let ell = ell();
+        assert_eq!(ell.locate(Point2::new(1, 1)), Location::Inside);
+        assert_eq!(ell.locate(Point2::new(3, 3)), Location::Inside);
+        assert_eq!(ell.locate(Point2::new(3, 1)), Location::Outside);
+        assert_eq!(ell.locate(Point2::new(2, 1)), Location::Boundary);
+        assert_eq!(ell.locate(Point2::new(2, 2)), Location::Boundary);
+        assert_eq!(ell.locate(Point2::new(0, 0)), Location::Boundary);
+        // On the level of a vertex, where a ray could cross two edges at once.
+        assert_eq!(ell.locate(Point2::new(-1, 2)), Location::Outside);
+        assert_eq!(ell.locate(Point2::new(1, 2)), Location::Inside);
+        assert_eq!(ell.locate(Point2::new(5, 4)), Location::Outside);
+    }
+
+    #[test]
+    fn rectangles_match_checking_every_half_point() {
+        let ell = ell();
+        for (x1, y1, x2, y2) in (-1..=5)
+            .flat_map(|x1| (-1..=5).map(move |y1| (x1, y1)))
+            .flat_map(|(x1, y1)| {
+                (x1..=5).flat_map(move |x2| (y1..=5).map(move |y2| (x1, y1, x2, y2)))
+            })
+        {
+            let expected = (2 * x1..=2 * x2).all(|x| {
+                (2 * y1..=2 * y2)
+                    .all(|y| ell.locate_doubled(x.into(), y.into()) != Location::Outside)
+            });
+            let (a, b) = (Point2::new(x1, y2), Point2::new(x2, y1));
+            assert_eq!(ell.contains_rectangle(a, b), expected, "{} {}", a, b);
+        }
+    }
+
+    #[test]
+    fn drawing_marks_every_cell_by_location() {
+        let ell = ell();
+        let plane = CompressedPlane::new(ell.vertices()).unwrap();
+        let grid = ell.draw(&plane);
+        for row in 0..plane.rows() {
+            for col in 0..plane.cols() {
+                let position = GridPosition(row, col);
+                let expected = match ell.locate(plane.point(position)) {
+                    Location::Boundary => '#',
+                    Location::Inside => 'X',
+                    Location::Outside => '.',
+                };
+                assert_eq!(grid.get_cell(position).unwrap().value, expected);
+            }
+        }
+    }
+}
@@ -0,0 +1,417 @@
+use crate::exact_cover::ExactCover;
+use crate::grid::{Grid, GridPosition};
+
+/// A set of cells, kept shifted so that its top row and leftmost column are
+/// both 0, and sorted so that equal shapes compare equal.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Polyomino {
+    cells: Vec<(usize, usize)>,
+}
+
+impl Polyomino {
+    /// The polyomino made of `cells`, as `(row, col)` pairs.
+    pub fn new(cells: impl IntoIterator<Item = (usize, usize)>) -> Self {
+        let cells: Vec<(usize, usize)> = cells.into_iter().collect();
+        let top = cells.iter().map(|&(r, _)| r).min().unwrap_or(0);
+        let left = cells.iter().map(|&(_, c)| c).min().unwrap_or(0);
+        let mut cells: Vec<(usize, usize)> =
+            cells.iter().map(|&(r, c)| (r - top, c - left)).collect();
+        cells.sort_unstable();
+        cells.dedup();
+        Polyomino { cells }
+    }
+
+    pub fn cells(&self) -> &[(usize, usize)] {
+        &self.cells
+    }
+
+    /// Number of cells.
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    /// Height of the bounding box.
+    pub fn rows(&self) -> usize {
+        self.cells.iter().map(|&(r, _)| r + 1).max().unwrap_or(0)
+    }
+
+    /// Width of the bounding box.
+    pub fn cols(&self) -> usize {
+        self.cells.iter().map(|&(_, c)| c + 1).max().unwrap_or(0)
+    }
+
+    /// Turned a quarter turn clockwise.
+    pub fn rotated(&self) -> Self {
+        let rows = self.rows();
+        Polyomino::new(self.cells.iter().map(|&(r, c)| (c, rows - 1 - r)))
+    }
+
+    /// Mirrored left to right.
+    pub fn reflected(&self) -> Self {
+        let cols = self.cols();
+        Polyomino::new(self.cells.iter().map(|&(r, c)| (r, cols - 1 - c)))
+    }
+
+    /// Every distinct way of turning and flipping the polyomino over, up to
+    /// eight, in a fixed order.
+    pub fn orientations(&self) -> Vec<Polyomino> {
+        let mut orientations = Vec::new();
+        for start in [self.clone(), self.reflected()] {
+            let mut current = start;
+            for _ in 0..4 {
+                let next = current.rotated();
+                orientations.push(current);
+                current = next;
+            }
+        }
+        orientations.sort();
+        orientations.dedup();
+        orientations
+    }
+}
+
+/// One piece of a [`Packing`]: which of the shapes it is and where it went.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Placement {
+    pub shape: usize,
+    pub cells: Vec<GridPosition>,
+}
+
+/// A way of packing pieces into a region without overlap.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Packing {
+    pub placements: Vec<Placement>,
+}
+
+impl Packing {
+    /// Mark each piece's cells on `grid` with a letter of its own, going
+    /// round the alphabet again after `z`.
+    pub fn draw(&self, grid: &mut Grid) {
+        const LETTERS: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz";
+        for (idx, placement) in self.placements.iter().enumerate() {
+            let letter = LETTERS[idx % LETTERS.len()] as char;
+            for &position in &placement.cells {
+                grid.update_cell(position, letter);
+            }
+        }
+    }
+}
+
+/// A way to fit `counts[i]` copies of each `shapes[i]` into a `rows` by
+/// `cols` region without any overlapping, turning and flipping them over as
+/// needed, `None` if there is none.
+///
+/// Regions without room for every cell are ruled out at once, and regions
+/// with room for every piece in a box of its own are packed that way. The
+/// rest are searched as an exact cover problem, with a column for each piece
+/// that must be placed and one for each cell that may be covered, leaving no
+/// more cells empty than the pieces leave spare. The search gives up on a
+/// way of placing pieces as soon as more cells than that can no longer be
+/// covered. Copies of the same shape are interchangeable, so they're only
+/// tried in one order.
+pub fn pack(rows: usize, cols: usize, shapes: &[Polyomino], counts: &[usize]) -> Option<Packing> {
+    assert_eq!(shapes.len(), counts.len(), "a count for every shape");
+    let area: usize = shapes
+        .iter()
+        .zip(counts)
+        .map(|(shape, &n)| shape.len() * n)
+        .sum();
+    if area > rows * cols {
+        return None;
+    }
+    if let Some(packing) = pack_in_boxes(rows, cols, shapes, counts) {
+        return Some(packing);
+    }
+
+    // Each copy of each shape, and every place each shape can go.
+    let pieces: Vec<usize> = counts
+        .iter()
+        .enumerate()
+        .flat_map(|(shape, &n)| std::iter::repeat_n(shape, n))
+        .collect();
+    let places: Vec<Vec<Vec<GridPosition>>> = shapes
+        .iter()
+        .map(|shape| {
+            let mut places = Vec::new();
+            for orientation in shape.orientations() {
+                let (height, width) = (orientation.rows(), orientation.cols());
+                for top in 0..(rows + 1).saturating_sub(height) {
+                    for left in 0..(cols + 1).saturating_sub(width) {
+                        let cells = orientation
+                            .cells()
+                            .iter()
+                            .map(|&(r, c)| GridPosition(top + r, left + c))
+                            .collect();
+                        places.push(cells);
+                    }
+                }
+            }
+            places
+        })
+        .collect();
+
+    let mut cover = ExactCover::new(pieces.len(), rows * cols);
+    // The piece and place of every row.
+    let mut options = Vec::new();
+    for (piece, &shape) in pieces.iter().enumerate() {
+        for (place, cells) in places[shape].iter().enumerate() {
+            let mut columns = vec![piece];
+            columns.extend(
+                cells
+                    .iter()
+                    .map(|position| pieces.len() + position.0 * cols + position.1),
+            );
+            cover.add_row(&columns);
+            options.push((piece, place));
+        }
+    }
+    // Copies of a shape are interchangeable, so each is only placed once the
+    // copies before it are. Their columns always have as many rows left as
+    // each other, so the search picks the earliest copy left to place.
+    let first_copies: Vec<usize> = counts
+        .iter()
+        .scan(0, |first, &n| Some(std::mem::replace(first, *first + n)))
+        .collect();
+    let chosen = cover.solve_leaving(rows * cols - area, |chosen, row| {
+        let shape = pieces[options[row].0];
+        let placed = chosen
+            .iter()
+            .filter(|&&other| pieces[options[other].0] == shape)
+            .count();
+        options[row].0 == first_copies[shape] + placed
+    })?;
+
+    let mut placements: Vec<(usize, Placement)> = chosen
+        .iter()
+        .map(|&row| {
+            let (piece, place) = options[row];
+            let shape = pieces[piece];
+            let cells = places[shape][place].clone();
+            (piece, Placement { shape, cells })
+        })
+        .collect();
+    placements.sort_by_key(|&(piece, _)| piece);
+    Some(Packing {
+        placements: placements
+            .into_iter()
+            .map(|(_, placement)| placement)
+            .collect(),
+    })
+}
+
+/// Put every piece in a box of its own, each box as big as the largest
+/// shape's bounding box, if there's room for enough of them.
+fn pack_in_boxes(
+    rows: usize,
+    cols: usize,
+    shapes: &[Polyomino],
+    counts: &[usize],
+) -> Option<Packing> {
+    let used = || shapes.iter().zip(counts).filter(|&(_, &n)| n > 0);
+    let height = used().map(|(shape, _)| shape.rows()).max().unwrap_or(1);
+    let width = used().map(|(shape, _)| shape.cols()).max().unwrap_or(1);
+    let across = cols / width.max(1);
+    let boxes = (rows / height.max(1)) * across;
+    if counts.iter().sum::<usize>() > boxes {
+        return None;
+    }
+
+    let mut placements = Vec::new();
+    for (shape, (polyomino, &n)) in shapes.iter().zip(counts).enumerate() {
+        for _ in 0..n {
+            let slot = placements.len();
+            let (top, left) = ((slot / across) * height, (slot % across) * width);
+            let cells = polyomino
+                .cells()
+                .iter()
+                .map(|&(r, c)| GridPosition(top + r, left + c))
+                .collect();
+            placements.push(Placement { shape, cells });
+        }
+    }
+    Some(Packing { placements })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn shape(picture: &str) -> Polyomino {
+        Polyomino::new(picture.lines().enumerate().flat_map(|(r, line)| {
+            line.chars()
+                .enumerate()
+                .filter(|&(_, c)| c == '#')
+                .map(move |(c, _)| (r, c))
+        }))
+    }
+
+    /// Check that `packing` puts `counts[i]` of each `shapes[i]`, each turned
+    /// some way, inside the region without overlapping.
+    fn check_packing(
+        rows: usize,
+        cols: usize,
+        shapes: &[Polyomino],
+        counts: &[usize],
+        packing: &Packing,
+    ) {
+        let mut used = vec![vec![false; cols]; rows];
+        let mut placed = vec![0; shapes.len()];
+        for placement in &packing.placements {
+            placed[placement.shape] += 1;
+            for &GridPosition(r, c) in &placement.cells {
+                assert!(r < rows && c < cols, "{:?} is outside", (r, c));
+                assert!(
+                    !std::mem::replace(&mut used[r][c], true),
+                    "{:?} overlaps",
+                    (r, c)
+                );
+            }
+            let piece = Polyomino::new(placement.cells.iter().map(|p| (p.0, p.1)));
+            assert!(shapes[placement.shape].orientations().contains(&piece));
+        }
+        assert_eq!(placed, counts);
+    }
+
+    #[test]
+    fn cells_are_shifted_sorted_and_deduplicated() {
+        let polyomino = Polyomino::new([(5, 3), (4, 4), (5, 3), (4, 3)]);
+        assert_eq!(polyomino.cells(), [(0, 0), (0, 1), (1, 0)]);
+        assert_eq!(
+            (polyomino.len(), polyomino.rows(), polyomino.cols()),
+            (3, 2, 2)
+        );
+        let empty = Polyomino::new([]);
+        assert!(empty.is_empty());
+        assert_eq!((empty.rows(), empty.cols()), (0, 0));
+        assert_eq!(empty.orientations(), [Polyomino::new([])]);
+    }
+
+    #[test]
+    fn turning_and_flipping() {
+        let l = shape("#.\n#.\n##");
+        assert_eq!(l.rotated(), shape("###\n#.."));
+        assert_eq!(l.reflected(), shape(".#\n.#\n##"));
+        assert_eq!(l.rotated().rotated().rotated().rotated(), l);
+        assert_eq!(l.reflected().reflected(), l);
+    }
+
+    #[test]
+    fn orientation_counts() {
+        for (picture, count) in [
+            ("#", 1),
+            ("##\n##", 1),
+            ("##", 2),
+            ("####", 2),
+            ("#.\n#.\n##", 8),
+            ("###\n.#.", 4),
+            (".##\n##.", 4),
+            (".#.\n###\n.#.", 1),
+            ("##.\n.##\n..#", 4),
+        ] {
+            let orientations = shape(picture).orientations();
+            assert_eq!(orientations.len(), count, "{}", picture);
+            assert!(orientations.is_sorted());
+        }
+    }
+
+    #[test]
+    fn nothing_to_pack() {
+        assert_eq!(pack(0, 0, &[], &[]), Some(Packing::default()));
+        assert_eq!(pack(2, 2, &[shape("##")], &[0]), Some(Packing::default()));
+    }
+
+    #[test]
+    fn too_little_room() {
+        assert_eq!(pack(2, 2, &[shape("##")], &[3]), None);
+        assert_eq!(pack(1, 4, &[shape("#.\n##")], &[1]), None);
+    }
+
+    #[test]
+    fn no_packing_despite_the_room() {
+        // The only place for the cross leaves four separate corners.
+        let shapes = [shape(".#.\n###\n.#."), shape("##")];
+        assert_eq!(pack(3, 3, &shapes, &[1, 1]), None);
+    }
+
+    #[test]
+    fn room_for_a_box_each() {
+        let shapes = [shape("#.\n##"), shape("###")];
+        let packing = pack(4, 6, &shapes, &[3, 1]).unwrap();
+        check_packing(4, 6, &shapes, &[3, 1], &packing);
+        // Boxes of the largest bounding box, 2 by 3, filled in reading order.
+        let tops: Vec<GridPosition> = packing.placements.iter().map(|p| p.cells[0]).collect();
+        assert_eq!(
+            tops,
+            [
+                GridPosition(0, 0),
+                GridPosition(0, 3),
+                GridPosition(2, 0),
+                GridPosition(2, 3)
+            ]
+        );
+    }
+
+    #[test]
+    fn pieces_that_need_turning_to_fit() {
+        let shapes = [shape("#.\n##")];
+        let packing = pack(2, 3, &shapes, &[2]).unwrap();
+        check_packing(2, 3, &shapes, &[2], &packing);
+
+        let shapes = [shape("#.\n#.\n##"), shape("##\n##")];
+        let packing = pack(4, 4, &shapes, &[2, 2]).unwrap();
+        check_packing(4, 4, &shapes, &[2, 2], &packing);
+    }
+
+    /// The presents of the puzzle's example.
+    fn presents() -> Vec<Polyomino> {
+        [
+            "###\n##.\n##.",
+            "###\n##.\n.##",
+            ".##\n###\n##.",
+            "##.\n###\n##.",
+            "###\n#..\n###",
+            "###\n.#.\n###",
+        ]
+        .map(shape)
+        .to_vec()
+    }
+
+    #[test]
+    fn the_puzzle_example() {
+        let shapes = presents();
+        let packing = pack(5, 12, &shapes, &[1, 0, 1, 0, 2, 2]).unwrap();
+        check_packing(5, 12, &shapes, &[1, 0, 1, 0, 2, 2], &packing);
+        assert_eq!(pack(5, 12, &shapes, &[1, 0, 1, 0, 3, 2]), None);
+    }
+
+    #[test]
+    fn tight_regions_are_searched_quickly() {
+        // 91 of the 100 cells, with too many presents for a box each.
+        let shapes = presents();
+        let counts = [2, 2, 2, 2, 2, 3];
+        let packing = pack(10, 10, &shapes, &counts).unwrap();
+        check_packing(10, 10, &shapes, &counts, &packing);
+        assert_eq!(pack(6, 12, &shapes, &[2, 0, 2, 0, 3, 3]), None);
+    }
+
+    #[test]
+    fn draw_letters_each_piece() {
+        let packing = pack(2, 3, &[shape("#.\n##")], &[2]).unwrap();
+        let mut grid: Grid = "...\n...\n".parse().unwrap();
+        packing.draw(&mut grid);
+        let drawn = grid.to_string();
+        assert_eq!(drawn.matches('A').count(), 3);
+        assert_eq!(drawn.matches('B').count(), 3);
+        assert!(!drawn.contains('.'));
+    }
+
+    #[test]
+    #[should_panic(expected = "a count for every shape")]
+    fn counts_must_match_the_shapes() {
+        pack(2, 2, &[shape("#")], &[]);
+    }
+}
@@ -0,0 +1,315 @@
+use crate::compress::PrefixSums;
+use crate::grid::{Grid, GridPosition};
+
+/// A block of grid cells, from its top left to its bottom right cell, and
+/// its area.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Rectangle {
+    pub top_left: GridPosition,
+    pub bottom_right: GridPosition,
+    pub area: u128,
+}
+
+/// Running totals of `weights`, with `totals[i]` the sum of the first `i`.
+fn totals(weights: &[u64]) -> Vec<u128> {
+    let mut totals = Vec::with_capacity(weights.len() + 1);
+    totals.push(0);
+    for &weight in weights {
+        totals.push(totals[totals.len() - 1] + u128::from(weight));
+    }
+    totals
+}
+
+fn is_allowed(grid: &Grid, allowed: &impl Fn(char) -> bool, position: GridPosition) -> bool {
+    grid.get_cell(position)
+        .is_some_and(|cell| allowed(cell.value))
+}
+
+/// The largest rectangle of cells whose value is `allowed`, with every cell
+/// counting as one. `None` if no cell is allowed.
+pub fn largest_rectangle(grid: &Grid, allowed: impl Fn(char) -> bool) -> Option<Rectangle> {
+    let heights = vec![1; grid.rows()];
+    let widths = vec![1; grid.cols()];
+    largest_weighted_rectangle(grid, allowed, &heights, &widths)
+}
+
+/// The largest rectangle of cells whose value is `allowed`, with each row
+/// `heights[row]` tall and each column `widths[col]` wide, like the slots of a
+/// compressed grid.
+///
+/// Each row is the base of a histogram of how far allowed cells reach up
+/// from it, and a monotonic stack finds the largest rectangle under each
+/// histogram, for `O(rows × cols)` overall.
+pub fn largest_weighted_rectangle(
+    grid: &Grid,
+    allowed: impl Fn(char) -> bool,
+    heights: &[u64],
+    widths: &[u64],
+) -> Option<Rectangle> {
+    let (rows, cols) = (grid.rows(), grid.cols());
+    let (row_totals, col_totals) = (totals(heights), totals(widths));
+    let mut reach = vec![0; cols];
+    let mut best: Option<Rectangle> = None;
+
+    for row in 0..rows {
+        for (col, reach) in reach.iter_mut().enumerate() {
+            if is_allowed(grid, &allowed, GridPosition(row, col)) {
+                *reach += 1;
+            } else {
+                *reach = 0;
+            }
+        }
+        // Bars by the column they start at and how many cells they reach up,
+        // increasing from bottom to top. The sentinel at the end clears it.
+        let mut stack: Vec<(usize, usize)> = Vec::new();
+        for col in 0..=cols {
+            let cells = reach.get(col).copied().unwrap_or(0);
+            let mut start = col;
+            while let Some(&(bar_start, bar_cells)) = stack.last()
+                && bar_cells >= cells
+            {
+                stack.pop();
+                start = bar_start;
+                if bar_cells == 0 {
+                    continue;
+                }
+                let top = row + 1 - bar_cells;
+                let area = (row_totals[row + 1] - row_totals[top])
+                    * (col_totals[col] - col_totals[bar_start]);
+                if best.is_none_or(|best| area > best.area) {
+                    best = Some(Rectangle {
+                        top_left: GridPosition(top, bar_start),
+                        bottom_right: GridPosition(row, col - 1),
+                        area,
+                    });
+                }
+            }
+            stack.push((start, cells));
+        }
+    }
+    best
+}
+
+/// The largest rectangle of cells whose value is `allowed` with opposite
+/// corners at two of the marked `corners`, sized as for
+/// [`largest_weighted_rectangle`].
+///
+/// Every pair of corners is checked in constant time against prefix sums of
+/// the cells that aren't allowed, for `O(rows × cols + corners²)` overall.
+pub fn largest_cornered_rectangle(
+    grid: &Grid,
+    allowed: impl Fn(char) -> bool,
+    corners: &[GridPosition],
+    heights: &[u64],
+    widths: &[u64],
+) -> Option<Rectangle> {
+    let (row_totals, col_totals) = (totals(heights), totals(widths));
+    let blocked = PrefixSums::new(grid.rows(), grid.cols(), |position| {
+        u128::from(!is_allowed(grid, &allowed, position))
+    });
+    let corners: Vec<GridPosition> = corners
+        .iter()
+        .copied()
+        .filter(|&corner| is_allowed(grid, &allowed, corner))
+        .collect();
+
+    let mut best: Option<Rectangle> = None;
+    for (idx, &a) in corners.iter().enumerate() {
+        for &b in &corners[idx + 1..] {
+            let top_left = GridPosition(a.0.min(b.0), a.1.min(b.1));
+            let bottom_right = GridPosition(a.0.max(b.0), a.1.max(b.1));
+            let area = (row_totals[bottom_right.0 + 1] - row_totals[top_left.0])
+                * (col_totals[bottom_right.1 + 1] - col_totals[top_left.1]);
+            if best.is_none_or(|best| area > best.area) && blocked.sum(a, b) == 0 {
+                best = Some(Rectangle {
+                    top_left,
+                    bottom_right,
+                    area,
+                });
+            }
+        }
+    }
+    best
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn grid(rows: &[&str]) -> Grid {
+        rows.join("\n").parse().unwrap()
+    }
+
+    /// Small grids with a scattering of blocked cells.
+    fn grids() -> Vec<Grid> {
+        let mut state: u32 = 12345;
+        (0..40)
+            .map(|idx| {
+                let (rows, cols) = (1 + idx % 5, 1 + idx / 5 % 6);
+                let cells: Vec<String> = (0..rows)
+                    .map(|_| {
+                        (0..cols)
+                            .map(|_| {
+                                state = state.wrapping_mul(1_103_515_245).wrapping_add(12345);
+                                if (state >> 16).is_multiple_of(4) { '#' } else { '.' }
+                            })
+                            .collect()
+                    })
+                    .collect();
+                cells.join("\n").parse().unwrap()
+            })
+            .collect()
+    }
+
+    fn weights(len: usize, seed: u64) -> Vec<u64> {
+        (0..len as u64).map(|i| (i * 7 + seed) % 5 + 1).collect()
+    }
+
+    fn open(grid: &Grid, a: GridPosition, b: GridPosition) -> bool {
+        (a.0.min(b.0)..=a.0.max(b.0)).all(|row| {
+            (a.1.min(b.1)..=a.1.max(b.1))
+                .all(|col| grid.get_cell(GridPosition(row, col)).unwrap().value == '.')
+        })
+    }
+
+    fn weighted_area(rectangle: &Rectangle, heights: &[u64], widths: &[u64]) -> u128 {
+        let (a, b) = (rectangle.top_left, rectangle.bottom_right);
+        let height: u64 = heights[a.0..=b.0].iter().sum();
+        let width: u64 = widths[a.1..=b.1].iter().sum();
+        u128::from(height) * u128::from(width)
+    }
+
+    /// The largest area of a rectangle with opposite corners at any two of
+    /// `corners`, or any two cells if there are none.
+    fn brute_force(
+        grid: &Grid,
+        heights: &[u64],
+        widths: &[u64],
+        corners: Option<&[GridPosition]>,
+    ) -> Option<u128> {
+        let cells: Vec<GridPosition> = match corners {
+            Some(corners) => corners.to_vec(),
+            None => (0..grid.rows())
+                .flat_map(|row| (0..grid.cols()).map(move |col| GridPosition(row, col)))
+                .collect(),
+        };
+        let mut best = None;
+        for (idx, &a) in cells.iter().enumerate() {
+            let others = if corners.is_some() {
+                &cells[idx + 1..]
+            } else {
+                &cells[idx..]
+            };
+            for &b in others {
+                if open(grid, a, b) {
+                    let rectangle = Rectangle {
+                        top_left: GridPosition(a.0.min(b.0), a.1.min(b.1)),
+                        bottom_right: GridPosition(a.0.max(b.0), a.1.max(b.1)),
+                        area: 0,
+                    };
+                    best = best.max(Some(weighted_area(&rectangle, heights, widths)));
+                }
+            }
+        }
+        best
+    }
+
+    fn check(
+        grid: &Grid,
+        found: Option<Rectangle>,
+        heights: &[u64],
+        widths: &[u64],
+        expected: Option<u128>,
+    ) {
+        assert_eq!(found.map(|r| r.area), expected, "{}", grid);
+        if let Some(found) = found {
+            assert!(open(grid, found.top_left, found.bottom_right));
+            assert_eq!(weighted_area(&found, heights, widths), found.area);
+        }
+    }
+
+    #[test]
+    fn largest_rectangle_of_a_small_grid() {
+        let grid = grid(&["..#..", "....#", "#....", "...#."]);
+        let found = largest_rectangle(&grid, |c| c == '.').unwrap();
+        assert_eq!(found.area, 6);
+        // Rows 1 to 3 of columns 1 and 2 are as large, but found later.
+        assert_eq!(found.top_left, GridPosition(1, 1));
+        assert_eq!(found.bottom_right, GridPosition(2, 3));
+    }
+
+    #[test]
+    fn nothing_allowed() {
+        let grid = grid(&["##", "##"]);
+        assert_eq!(largest_rectangle(&grid, |c| c == '.'), None);
+        let corners = [GridPosition(0, 0), GridPosition(1, 1)];
+        assert_eq!(
+            largest_cornered_rectangle(&grid, |c| c == '.', &corners, &[1, 1], &[1, 1]),
+            None
+        );
+    }
+
+    #[test]
+    fn single_cell() {
+        let grid = grid(&["#.", "##"]);
+        let found = largest_rectangle(&grid, |c| c == '.').unwrap();
+        assert_eq!(
+            (found.top_left, found.bottom_right, found.area),
+            (GridPosition(0, 1), GridPosition(0, 1), 1)
+        );
+        // A rectangle needs two different corners.
+        let corners = [GridPosition(0, 1)];
+        assert_eq!(
+            largest_cornered_rectangle(&grid, |c| c == '.', &corners, &[1, 1], &[1, 1]),
+            None
+        );
+        assert_eq!(
+            largest_cornered_rectangle(&grid, |c| c == '.', &[], &[1, 1], &[1, 1]),
+            None
+        );
+    }
+
+    #[test]
+    fn weighted_rectangles_match_brute_force() {
+        for (idx, grid) in grids().iter().enumerate() {
+            let (heights, widths) = (weights(grid.rows(), idx as u64), weights(grid.cols(), 3));
+            let found = largest_weighted_rectangle(grid, |c| c == '.', &heights, &widths);
+            check(
+                grid,
+                found,
+                &heights,
+                &widths,
+                brute_force(grid, &heights, &widths, None),
+            );
+
+            let ones = (vec![1; grid.rows()], vec![1; grid.cols()]);
+            let found = largest_rectangle(grid, |c| c == '.');
+            check(
+                grid,
+                found,
+                &ones.0,
+                &ones.1,
+                brute_force(grid, &ones.0, &ones.1, None),
+            );
+        }
+    }
+
+    #[test]
+    fn cornered_rectangles_match_brute_force() {
+        for (idx, grid) in grids().iter().enumerate() {
+            let (heights, widths) = (weights(grid.rows(), idx as u64), weights(grid.cols(), 1));
+            let corners: Vec<GridPosition> = (0..grid.rows())
+                .flat_map(|row| (0..grid.cols()).map(move |col| GridPosition(row, col)))
+                .filter(|position| (position.0 + 2 * position.1 + idx) % 3 == 0)
+                .collect();
+            let allowed: Vec<GridPosition> = corners
+                .iter()
+                .copied()
+                .filter(|&corner| open(grid, corner, corner))
+                .collect();
+            let found = largest_cornered_rectangle(grid, |c| c == '.', &corners, &heights, &widths);
+            let expected = brute_force(grid, &heights, &widths, Some(&allowed));
+            check(grid, found, &heights, &widths, expected);
+        }
+    }
+}
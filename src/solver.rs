@@ -0,0 +1,604 @@
+use crate::ilp::{self, Program};
+use std::fmt::{Display, Formatter};
+use std::iter::Sum;
+use std::ops::{Add, Bound, Mul, Neg, RangeBounds, Sub};
+
+#[cfg(feature = "z3")]
+mod z3;
+
+#[cfg(feature = "z3")]
+pub use self::z3::Z3;
+
+/// An integer variable of a [`Problem`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Variable(usize);
+
+impl Variable {
+    /// Position among the problem's variables, in the order they were made.
+    pub fn index(self) -> usize {
+        self.0
+    }
+}
+
+/// A linear expression `Σ coefficient · variable + constant`, built up from
+/// variables and constants with `+`, `-` and `*`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Expression {
+    terms: Vec<(Variable, i64)>,
+    constant: i64,
+}
+
+impl Expression {
+    /// Each variable with its coefficient, possibly with repeats.
+    pub fn terms(&self) -> &[(Variable, i64)] {
+        &self.terms
+    }
+
+    pub fn constant(&self) -> i64 {
+        self.constant
+    }
+
+    /// A coefficient for each of the first `variables` variables, with
+    /// repeats added together.
+    pub fn coefficients(&self, variables: usize) -> Vec<i64> {
+        let mut coefficients = vec![0; variables];
+        for &(variable, coefficient) in &self.terms {
+            coefficients[variable.0] += coefficient;
+        }
+        coefficients
+    }
+}
+
+impl From<Variable> for Expression {
+    fn from(variable: Variable) -> Self {
+        Expression {
+            terms: vec![(variable, 1)],
+            constant: 0,
+        }
+    }
+}
+
+impl From<i64> for Expression {
+    fn from(constant: i64) -> Self {
+        Expression {
+            terms: Vec::new(),
+            constant,
+        }
+    }
+}
+
+impl<T: Into<Expression>> Add<T> for Expression {
+    type Output = Expression;
+
+    fn add(mut self, other: T) -> Expression {
+        let other = other.into();
+        self.terms.extend(other.terms);
+        self.constant += other.constant;
+        self
+    }
+}
+
+impl<T: Into<Expression>> Sub<T> for Expression {
+    type Output = Expression;
+
+    fn sub(self, other: T) -> Expression {
+        self + -other.into()
+    }
+}
+
+impl Neg for Expression {
+    type Output = Expression;
+
+    fn neg(self) -> Expression {
+        self * -1
+    }
+}
+
+impl Mul<i64> for Expression {
+    type Output = Expression;
+
+    fn mul(mut self, factor: i64) -> Expression {
+        for (_, coefficient) in &mut self.terms {
+            *coefficient *= factor;
+        }
+        self.constant *= factor;
+        self
+    }
+}
+
+impl<T: Into<Expression>> Add<T> for Variable {
+    type Output = Expression;
+
+    fn add(self, other: T) -> Expression {
+        Expression::from(self) + other
+    }
+}
+
+impl<T: Into<Expression>> Sub<T> for Variable {
+    type Output = Expression;
+
+    fn sub(self, other: T) -> Expression {
+        Expression::from(self) - other
+    }
+}
+
+impl Mul<i64> for Variable {
+    type Output = Expression;
+
+    fn mul(self, factor: i64) -> Expression {
+        Expression::from(self) * factor
+    }
+}
+
+impl<T: Into<Expression>> Sum<T> for Expression {
+    fn sum<I: Iterator<Item = T>>(iter: I) -> Self {
+        iter.fold(Expression::default(), |sum, term| sum + term)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Relation {
+    Equal,
+    AtMost,
+    AtLeast,
+}
+
+/// The constraint `expression (= | ≤ | ≥) 0`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Constraint {
+    pub expression: Expression,
+    pub relation: Relation,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Goal {
+    Minimise,
+    Maximise,
+}
+
+/// An integer linear program: integer variables, each optionally bounded,
+/// linear constraints on them and optionally a linear objective.
+#[derive(Debug, Clone, Default)]
+pub struct Problem {
+    bounds: Vec<(Option<i64>, Option<i64>)>,
+    constraints: Vec<Constraint>,
+    objective: Option<(Goal, Expression)>,
+}
+
+impl Problem {
+    pub fn new() -> Self {
+        Problem::default()
+    }
+
+    /// A new integer variable in `range`, like `0..` or `-5..=5`.
+    pub fn integer(&mut self, range: impl RangeBounds<i64>) -> Variable {
+        let lower = match range.start_bound() {
+            Bound::Included(&lower) => Some(lower),
+            Bound::Excluded(&lower) => Some(lower + 1),
+            Bound::Unbounded => None,
+        };
+        let upper = match range.end_bound() {
+            Bound::Included(&upper) => Some(upper),
+            Bound::Excluded(&upper) => Some(upper - 1),
+            Bound::Unbounded => None,
+        };
+        self.bounds.push((lower, upper));
+        Variable(self.bounds.len() - 1)
+    }
+
+    /// Require `lhs (= | ≤ | ≥) rhs`.
+    pub fn constrain(
+        &mut self,
+        lhs: impl Into<Expression>,
+        relation: Relation,
+        rhs: impl Into<Expression>,
+    ) {
+        self.constraints.push(Constraint {
+            expression: lhs.into() - rhs,
+            relation,
+        });
+    }
+
+    pub fn equal(&mut self, lhs: impl Into<Expression>, rhs: impl Into<Expression>) {
+        self.constrain(lhs, Relation::Equal, rhs);
+    }
+
+    pub fn at_most(&mut self, lhs: impl Into<Expression>, rhs: impl Into<Expression>) {
+        self.constrain(lhs, Relation::AtMost, rhs);
+    }
+
+    pub fn at_least(&mut self, lhs: impl Into<Expression>, rhs: impl Into<Expression>) {
+        self.constrain(lhs, Relation::AtLeast, rhs);
+    }
+
+    /// Look for the solution with the smallest `objective`, replacing any
+    /// objective set before.
+    pub fn minimise(&mut self, objective: impl Into<Expression>) {
+        self.objective = Some((Goal::Minimise, objective.into()));
+    }
+
+    /// Look for the solution with the largest `objective`, replacing any
+    /// objective set before.
+    pub fn maximise(&mut self, objective: impl Into<Expression>) {
+        self.objective = Some((Goal::Maximise, objective.into()));
+    }
+
+    /// Number of variables.
+    pub fn variables(&self) -> usize {
+        self.bounds.len()
+    }
+
+    /// The lower and upper bounds of `variable`, `None` if unbounded.
+    pub fn bounds(&self, variable: Variable) -> (Option<i64>, Option<i64>) {
+        self.bounds[variable.0]
+    }
+
+    pub fn constraints(&self) -> &[Constraint] {
+        &self.constraints
+    }
+
+    pub fn objective(&self) -> Option<(Goal, &Expression)> {
+        self.objective
+            .as_ref()
+            .map(|(goal, objective)| (*goal, objective))
+    }
+
+    /// Solve with Z3 when built with the `z3` feature, otherwise with the
+    /// [`BuiltIn`] solver.
+    pub fn solve(&self) -> Result<Solution, SolveError> {
+        #[cfg(feature = "z3")]
+        let backend = Z3;
+        #[cfg(not(feature = "z3"))]
+        let backend = BuiltIn;
+        backend.solve(self)
+    }
+}
+
+/// A value for every variable of a [`Problem`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Solution {
+    values: Vec<i64>,
+}
+
+impl Solution {
+    pub fn new(values: Vec<i64>) -> Self {
+        Solution { values }
+    }
+
+    pub fn value(&self, variable: Variable) -> i64 {
+        self.values[variable.0]
+    }
+
+    /// The value of `expression` with every variable set to its solution.
+    pub fn evaluate(&self, expression: &Expression) -> i64 {
+        expression
+            .terms
+            .iter()
+            .map(|&(variable, coefficient)| coefficient * self.value(variable))
+            .sum::<i64>()
+            + expression.constant
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SolveError {
+    /// No assignment satisfies every constraint.
+    Infeasible,
+    /// The objective can be made as good as you like.
+    Unbounded,
+    /// The backend can't handle the problem, with the reason.
+    Unsupported(String),
+    /// The backend gave up, with the reason.
+    Unknown(String),
+}
+
+impl Display for SolveError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SolveError::Infeasible => write!(f, "the constraints have no solution"),
+            SolveError::Unbounded => write!(f, "the objective is unbounded"),
+            SolveError::Unsupported(reason) => write!(f, "unsupported problem: {reason}"),
+            SolveError::Unknown(reason) => write!(f, "the solver gave up: {reason}"),
+        }
+    }
+}
+
+/// Something that can solve a [`Problem`].
+pub trait Backend {
+    fn solve(&self, problem: &Problem) -> Result<Solution, SolveError>;
+}
+
+/// The solver in this crate, for small problems where every variable is
+/// bounded, either directly or by the constraints.
+///
+/// Bounds are first tightened by propagating them through the constraints,
+/// then the equalities are eliminated and every value of the remaining free
+/// variables within their bounds is searched, see [`IntegerSystem`].
+///
+/// [`IntegerSystem`]: crate::ilp::IntegerSystem
+#[derive(Debug, Clone, Copy, Default)]
+pub struct BuiltIn;
+
+/// How many passes of bound propagation to make at most, as bounds can keep
+/// creeping in one step at a time when the constraints are contradictory.
+const PROPAGATION_PASSES: usize = 100;
+
+impl Backend for BuiltIn {
+    fn solve(&self, problem: &Problem) -> Result<Solution, SolveError> {
+        let variables = problem.variables();
+        let mut equalities = Vec::new();
+        let mut inequalities = Vec::new();
+        for constraint in &problem.constraints {
+            let coefficients = constraint.expression.coefficients(variables);
+            let value = -constraint.expression.constant;
+            let negated = || coefficients.iter().map(|&c| -c).collect();
+            match constraint.relation {
+                Relation::Equal => equalities.push((coefficients, value)),
+                Relation::AtMost => inequalities.push((coefficients, value)),
+                Relation::AtLeast => inequalities.push((negated(), -value)),
+            }
+        }
+        let objective = match &problem.objective {
+            None => vec![0; variables],
+            Some((Goal::Minimise, objective)) => objective.coefficients(variables),
+            Some((Goal::Maximise, objective)) => (-objective.clone()).coefficients(variables),
+        };
+
+        let bounds = bound(problem, &equalities, &inequalities, &objective)?;
+        let program = Program {
+            variables,
+            equalities,
+            inequalities,
+            bounds,
+            objective,
+        };
+        ilp::optimise(&program)
+            .map(Solution::new)
+            .ok_or(SolveError::Infeasible)
+    }
+}
+
+/// Finite bounds for every variable, tightened by propagating them through
+/// the constraints. A variable that no constraint mentions is fixed at the
+/// bound the objective prefers.
+fn bound(
+    problem: &Problem,
+    equalities: &[(Vec<i64>, i64)],
+    inequalities: &[(Vec<i64>, i64)],
+    objective: &[i64],
+) -> Result<Vec<(i64, i64)>, SolveError> {
+    let mut bounds: Vec<(Option<i128>, Option<i128>)> = problem
+        .bounds
+        .iter()
+        .map(|&(lower, upper)| (lower.map(i128::from), upper.map(i128::from)))
+        .collect();
+
+    // Every constraint as `a · x ≤ b`, equalities both ways round.
+    let mut rows: Vec<(Vec<i128>, i128)> = Vec::new();
+    for (coefficients, value) in equalities {
+        let row: Vec<i128> = coefficients.iter().map(|&c| i128::from(c)).collect();
+        rows.push((row.iter().map(|&c| -c).collect(), -i128::from(*value)));
+        rows.push((row, i128::from(*value)));
+    }
+    for (coefficients, value) in inequalities {
+        rows.push((
+            coefficients.iter().map(|&c| i128::from(c)).collect(),
+            i128::from(*value),
+        ));
+    }
+
+    for _ in 0..PROPAGATION_PASSES {
+        let mut changed = false;
+        for (coefficients, value) in &rows {
+            // The smallest each term can be, where it's bounded.
+            let smallest: Vec<Option<i128>> = coefficients
+                .iter()
+                .zip(&bounds)
+                .map(|(&c, &(lower, upper))| match c.signum() {
+                    0 => Some(0),
+                    1 => lower.map(|lower| c * lower),
+                    _ => upper.map(|upper| c * upper),
+                })
+                .collect();
+            let unbounded = smallest.iter().filter(|term| term.is_none()).count();
+            let total: i128 = smallest.iter().flatten().sum();
+            for (variable, &c) in coefficients.iter().enumerate() {
+                if c == 0 {
+                    continue;
+                }
+                // The least the other terms can add up to.
+                let rest = match (smallest[variable], unbounded) {
+                    (Some(own), 0) => total - own,
+                    (None, 1) => total,
+                    _ => continue,
+                };
+                let limit = value - rest;
+                let (lower, upper) = &mut bounds[variable];
+                if c > 0 {
+                    let tighter = limit.div_euclid(c);
+                    if upper.is_none_or(|upper| tighter < upper) {
+                        *upper = Some(tighter);
+                        changed = true;
+                    }
+                } else {
+                    let tighter = -(limit.div_euclid(-c));
+                    if lower.is_none_or(|lower| tighter > lower) {
+                        *lower = Some(tighter);
+                        changed = true;
+                    }
+                }
+                if let (Some(lower), Some(upper)) = bounds[variable]
+                    && lower > upper
+                {
+                    return Err(SolveError::Infeasible);
+                }
+            }
+        }
+        if !changed {
+            break;
+        }
+    }
+
+    let mentioned = |variable: usize| rows.iter().any(|(row, _)| row[variable] != 0);
+    bounds
+        .iter()
+        .enumerate()
+        .map(|(variable, &(lower, upper))| {
+            let (lower, upper) = match (lower, upper) {
+                (Some(lower), Some(upper)) => (lower, upper),
+                _ if mentioned(variable) => {
+                    return Err(SolveError::Unsupported(format!(
+                        "the built-in solver needs bounds on every variable, \
+                         but variable {variable} has none"
+                    )));
+                }
+                // Free to take whichever value the objective likes best.
+                (lower, upper) => {
+                    let value = match objective[variable].signum() {
+                        0 => lower.or(upper).unwrap_or(0),
+                        1 => lower.ok_or(SolveError::Unbounded)?,
+                        _ => upper.ok_or(SolveError::Unbounded)?,
+                    };
+                    (value, value)
+                }
+            };
+            let fit = |value: i128| {
+                i64::try_from(value).map_err(|_| {
+                    SolveError::Unsupported(format!("variable {variable} has too wide a range"))
+                })
+            };
+            Ok((fit(lower)?, fit(upper)?))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn expressions_collect_terms_and_constants() {
+        let mut problem = Problem::new();
+        let (x, y) = (problem.integer(0..), problem.integer(0..));
+        let expression = x * 2 + y - x + 5 - (y * 3 - 1);
+        assert_eq!(expression.coefficients(2), [1, -2]);
+        assert_eq!(expression.constant(), 6);
+        assert_eq!(expression.terms().len(), 4);
+
+        let sum: Expression = [x, y, x].into_iter().sum();
+        assert_eq!(sum.coefficients(2), [2, 1]);
+        let empty: Expression = std::iter::empty::<Variable>().sum();
+        assert_eq!(empty, Expression::default());
+
+        let solution = Solution::new(vec![4, 10]);
+        assert_eq!(solution.evaluate(&expression), 4 - 20 + 6);
+    }
+
+    #[test]
+    fn ranges_become_bounds() {
+        let mut problem = Problem::new();
+        let variables = [
+            problem.integer(..),
+            problem.integer(0..),
+            problem.integer(-5..=5),
+            problem.integer(-5..5),
+            problem.integer(..=3),
+        ];
+        let bounds: Vec<_> = variables.iter().map(|&v| problem.bounds(v)).collect();
+        assert_eq!(
+            bounds,
+            [
+                (None, None),
+                (Some(0), None),
+                (Some(-5), Some(5)),
+                (Some(-5), Some(4)),
+                (None, Some(3)),
+            ]
+        );
+        assert_eq!(variables[3].index(), 3);
+    }
+
+    #[test]
+    fn maximise_with_inequalities() {
+        let mut problem = Problem::new();
+        let (x, y) = (problem.integer(0..=3), problem.integer(0..));
+        problem.at_most(x + y, 4);
+        problem.at_most(x + y * 3, 6);
+        problem.maximise(x * 3 + y * 2);
+        let solution = BuiltIn.solve(&problem).unwrap();
+        assert_eq!((solution.value(x), solution.value(y)), (3, 1));
+    }
+
+    #[test]
+    fn minimise_with_an_equality() {
+        let mut problem = Problem::new();
+        let (x, y) = (problem.integer(0..), problem.integer(0..));
+        problem.equal(x * 2 + y * 3, 12);
+        problem.minimise(x + y);
+        let solution = BuiltIn.solve(&problem).unwrap();
+        assert_eq!((solution.value(x), solution.value(y)), (0, 4));
+        problem.maximise(x + y);
+        let solution = BuiltIn.solve(&problem).unwrap();
+        assert_eq!((solution.value(x), solution.value(y)), (6, 0));
+    }
+
+    #[test]
+    fn at_least_constraints_and_negative_values() {
+        let mut problem = Problem::new();
+        let x = problem.integer(-10..=10);
+        problem.at_least(x * 3, -7);
+        problem.minimise(x);
+        assert_eq!(BuiltIn.solve(&problem).unwrap().value(x), -2);
+    }
+
+    #[test]
+    fn infeasible_problems() {
+        let mut contradictory = Problem::new();
+        let x = contradictory.integer(0..);
+        contradictory.at_least(x, 3);
+        contradictory.at_most(x, 2);
+        assert_eq!(BuiltIn.solve(&contradictory), Err(SolveError::Infeasible));
+
+        let mut odd = Problem::new();
+        let y = odd.integer(0..=10);
+        odd.equal(y * 2, 7);
+        assert_eq!(BuiltIn.solve(&odd), Err(SolveError::Infeasible));
+
+        let mut empty = Problem::new();
+        empty.integer(1..1);
+        assert_eq!(BuiltIn.solve(&empty), Err(SolveError::Infeasible));
+    }
+
+    #[test]
+    fn unbounded_and_unsupported_problems() {
+        let mut unbounded = Problem::new();
+        let x = unbounded.integer(..=5);
+        unbounded.minimise(x);
+        assert_eq!(BuiltIn.solve(&unbounded), Err(SolveError::Unbounded));
+
+        let mut unsupported = Problem::new();
+        let (a, b) = (unsupported.integer(..), unsupported.integer(..));
+        unsupported.equal(a + b, 3);
+        assert!(matches!(
+            BuiltIn.solve(&unsupported),
+            Err(SolveError::Unsupported(_))
+        ));
+    }
+
+    #[test]
+    fn unconstrained_variables_take_the_preferred_bound() {
+        let mut problem = Problem::new();
+        let (x, y, z) = (
+            problem.integer(2..=9),
+            problem.integer(..=4),
+            problem.integer(..),
+        );
+        problem.maximise(x + y);
+        let solution = BuiltIn.solve(&problem).unwrap();
+        assert_eq!(
+            (solution.value(x), solution.value(y), solution.value(z)),
+            (9, 4, 0)
+        );
+        assert_eq!(BuiltIn.solve(&Problem::new()), Ok(Solution::new(vec![])));
+    }
+}
@@ -0,0 +1,72 @@
+use super::{Backend, Expression, Goal, Problem, Relation, Solution, SolveError};
+use ::z3::ast::Int;
+use ::z3::{Optimize, SatResult};
+
+/// Solves a [`Problem`] with Z3's optimiser, which needs the native Z3
+/// library.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Z3;
+
+/// `expression` as a Z3 integer term over `variables`.
+fn term(expression: &Expression, variables: &[Int]) -> Int {
+    let mut terms = vec![Int::from_i64(expression.constant())];
+    for &(variable, coefficient) in expression.terms() {
+        terms.push(Int::mul(&[
+            Int::from_i64(coefficient),
+            variables[variable.index()].clone(),
+        ]));
+    }
+    Int::add(&terms)
+}
+
+impl Backend for Z3 {
+    fn solve(&self, problem: &Problem) -> Result<Solution, SolveError> {
+        let optimize = Optimize::new();
+        let variables: Vec<Int> = (0..problem.variables())
+            .map(|idx| Int::new_const(format!("x{idx}").as_str()))
+            .collect();
+        for (variable, &(lower, upper)) in variables.iter().zip(&problem.bounds) {
+            if let Some(lower) = lower {
+                optimize.assert(&variable.ge(lower));
+            }
+            if let Some(upper) = upper {
+                optimize.assert(&variable.le(upper));
+            }
+        }
+        for constraint in problem.constraints() {
+            let expression = term(&constraint.expression, &variables);
+            optimize.assert(&match constraint.relation {
+                Relation::Equal => expression.eq(0),
+                Relation::AtMost => expression.le(0),
+                Relation::AtLeast => expression.ge(0),
+            });
+        }
+        match problem.objective() {
+            Some((Goal::Minimise, objective)) => optimize.minimize(&term(objective, &variables)),
+            Some((Goal::Maximise, objective)) => optimize.maximize(&term(objective, &variables)),
+            None => {}
+        }
+
+        match optimize.check(&[]) {
+            SatResult::Sat => {}
+            SatResult::Unsat => return Err(SolveError::Infeasible),
+            SatResult::Unknown => {
+                let reason = optimize.get_reason_unknown().unwrap_or_default();
+                return Err(SolveError::Unknown(reason));
+            }
+        }
+        let model = optimize
+            .get_model()
+            .ok_or_else(|| SolveError::Unknown("no model".to_string()))?;
+        let values = variables
+            .iter()
+            .map(|variable| {
+                model
+                    .eval(variable, true)
+                    .and_then(|value| value.as_i64())
+                    .ok_or_else(|| SolveError::Unknown("a value doesn't fit in an i64".to_string()))
+            })
+            .collect::<Result<Vec<i64>, SolveError>>()?;
+        Ok(Solution::new(values))
+    }
+}
@@ -0,0 +1,385 @@
+use crate::disjoint_set::{DisjointSet, Merge};
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+
+/// Exact squared Euclidean distance. Panics if it doesn't fit in a `u128`,
+/// which never happens with up to three axes while the points are less than
+/// 2^63 apart along every one of them.
+pub fn squared_distance<const D: usize>(a: [i64; D], b: [i64; D]) -> u128 {
+    a.iter()
+        .zip(b)
+        .try_fold(0u128, |sum, (&a, b)| {
+            sum.checked_add(u128::from(a.abs_diff(b)).pow(2))
+        })
+        .expect("squared distance overflows a u128")
+}
+
+/// Two points by index, `a < b`, and the squared distance between them.
+/// Ordered by distance, then by index, so that ties come out the same way
+/// every time.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Pair {
+    pub squared_distance: u128,
+    pub a: usize,
+    pub b: usize,
+}
+
+#[derive(Debug, Clone)]
+struct Node<const D: usize> {
+    point: usize,
+    left: Option<usize>,
+    right: Option<usize>,
+    /// Bounding box of every point in the subtree.
+    min: [i64; D],
+    max: [i64; D],
+    /// Largest point index in the subtree.
+    max_index: usize,
+}
+
+/// A k-d tree over integer points, like `Point3<i64>` or `[i64; 3]`, for
+/// nearest neighbour searches.
+#[derive(Debug, Clone)]
+pub struct KdTree<const D: usize> {
+    points: Vec<[i64; D]>,
+    nodes: Vec<Node<D>>,
+    root: Option<usize>,
+}
+
+impl<const D: usize> KdTree<D> {
+    pub fn new<P: Copy + Into<[i64; D]>>(points: &[P]) -> Self {
+        let mut tree = KdTree {
+            points: points.iter().map(|&point| point.into()).collect(),
+            nodes: Vec::with_capacity(points.len()),
+            root: None,
+        };
+        let mut indices: Vec<usize> = (0..points.len()).collect();
+        tree.root = tree.build(&mut indices, 0);
+        tree
+    }
+
+    fn build(&mut self, indices: &mut [usize], depth: usize) -> Option<usize> {
+        if indices.is_empty() {
+            return None;
+        }
+        let axis = depth % D;
+        let mid = indices.len() / 2;
+        let points = &self.points;
+        indices.select_nth_unstable_by_key(mid, |&i| (points[i][axis], i));
+        let point = indices[mid];
+        let (below, rest) = indices.split_at_mut(mid);
+        let left = self.build(below, depth + 1);
+        let right = self.build(&mut rest[1..], depth + 1);
+
+        let mut node = Node {
+            point,
+            left,
+            right,
+            min: self.points[point],
+            max: self.points[point],
+            max_index: point,
+        };
+        for child in [left, right].into_iter().flatten() {
+            let child = &self.nodes[child];
+            for axis in 0..D {
+                node.min[axis] = node.min[axis].min(child.min[axis]);
+                node.max[axis] = node.max[axis].max(child.max[axis]);
+            }
+            node.max_index = node.max_index.max(child.max_index);
+        }
+        self.nodes.push(node);
+        Some(self.nodes.len() - 1)
+    }
+
+    pub fn points(&self) -> &[[i64; D]] {
+        &self.points
+    }
+
+    /// Every point, nearest to `query` first and by index between equally
+    /// near points, found incrementally as the iterator is advanced.
+    pub fn nearest(&self, query: impl Into<[i64; D]>) -> Neighbors<'_, D> {
+        Neighbors::new(self, query.into(), 0)
+    }
+
+    /// Every pair of points, nearest first, found lazily without building
+    /// the full list of pairs.
+    pub fn nearest_pairs(&self) -> NearestPairs<'_, D> {
+        let searches: Vec<Neighbors<D>> = (0..self.points.len())
+            .map(|a| Neighbors::new(self, self.points[a], a + 1))
+            .collect();
+        let mut pairs = NearestPairs {
+            searches,
+            queue: BinaryHeap::new(),
+        };
+        for a in 0..pairs.searches.len() {
+            pairs.advance(a);
+        }
+        pairs
+    }
+
+    /// Squared distance from `query` to the closest point of a node's
+    /// bounding box.
+    fn bound(&self, node: &Node<D>, query: [i64; D]) -> u128 {
+        let mut closest = query;
+        for axis in 0..D {
+            closest[axis] = query[axis].clamp(node.min[axis], node.max[axis]);
+        }
+        squared_distance(query, closest)
+    }
+}
+
+/// Something waiting in a nearest neighbour search. Subtrees sort before
+/// points at the same distance so that a tie is always broken by index.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum Candidate {
+    Subtree(usize),
+    Point(usize),
+}
+
+/// Incremental nearest neighbour search, see [`KdTree::nearest`].
+#[derive(Debug, Clone)]
+pub struct Neighbors<'a, const D: usize> {
+    tree: &'a KdTree<D>,
+    query: [i64; D],
+    /// Only points with at least this index are returned.
+    first_index: usize,
+    queue: BinaryHeap<Reverse<(u128, Candidate)>>,
+}
+
+impl<'a, const D: usize> Neighbors<'a, D> {
+    fn new(tree: &'a KdTree<D>, query: [i64; D], first_index: usize) -> Self {
+        let mut neighbors = Neighbors {
+            tree,
+            query,
+            first_index,
+            queue: BinaryHeap::new(),
+        };
+        if let Some(root) = tree.root {
+            neighbors.push_subtree(root);
+        }
+        neighbors
+    }
+
+    fn push_subtree(&mut self, id: usize) {
+        let node = &self.tree.nodes[id];
+        if node.max_index >= self.first_index {
+            let bound = self.tree.bound(node, self.query);
+            self.queue.push(Reverse((bound, Candidate::Subtree(id))));
+        }
+    }
+}
+
+impl<const D: usize> Iterator for Neighbors<'_, D> {
+    /// The squared distance and index of the next nearest point.
+    type Item = (u128, usize);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some(Reverse((distance, candidate))) = self.queue.pop() {
+            match candidate {
+                Candidate::Point(point) => return Some((distance, point)),
+                Candidate::Subtree(id) => {
+                    let node = &self.tree.nodes[id];
+                    let (point, left, right) = (node.point, node.left, node.right);
+                    if point >= self.first_index {
+                        let distance = squared_distance(self.query, self.tree.points[point]);
+                        self.queue
+                            .push(Reverse((distance, Candidate::Point(point))));
+                    }
+                    for child in [left, right].into_iter().flatten() {
+                        self.push_subtree(child);
+                    }
+                }
+            }
+        }
+        None
+    }
+}
+
+/// Lazy enumeration of every pair of points, see [`KdTree::nearest_pairs`].
+///
+/// Each point searches outwards for neighbours with a larger index, and a
+/// shared queue holds the next pair from every point's search.
+#[derive(Debug, Clone)]
+pub struct NearestPairs<'a, const D: usize> {
+    searches: Vec<Neighbors<'a, D>>,
+    queue: BinaryHeap<Reverse<Pair>>,
+}
+
+impl<const D: usize> NearestPairs<'_, D> {
+    fn advance(&mut self, a: usize) {
+        if let Some((squared_distance, b)) = self.searches[a].next() {
+            self.queue.push(Reverse(Pair {
+                squared_distance,
+                a,
+                b,
+            }));
+        }
+    }
+}
+
+impl<const D: usize> Iterator for NearestPairs<'_, D> {
+    type Item = Pair;
+
+    fn next(&mut self) -> Option<Pair> {
+        let Reverse(pair) = self.queue.pop()?;
+        self.advance(pair.a);
+        Some(pair)
+    }
+}
+
+/// The edges of a minimum spanning tree over `points`, shortest first, found
+/// with Kruskal's algorithm over the lazily enumerated nearest pairs. The
+/// last edge is the one that finally connects every point.
+pub fn minimum_spanning_tree<const D: usize, P: Copy + Into<[i64; D]>>(points: &[P]) -> Vec<Pair> {
+    let tree = KdTree::new(points);
+    let mut components = DisjointSet::new(points.len());
+    let mut edges = Vec::with_capacity(points.len().saturating_sub(1));
+    if points.len() < 2 {
+        return edges;
+    }
+    for pair in tree.nearest_pairs() {
+        match components.union(pair.a, pair.b) {
+            Merge::Same => {}
+            Merge::Joined => edges.push(pair),
+            Merge::Unified => {
+                edges.push(pair);
+                break;
+            }
+        }
+    }
+    edges
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Points scattered over a small cube, so that some coincide and many
+    /// distances tie.
+    fn scattered(count: usize) -> Vec<[i64; 3]> {
+        let mut state: u64 = 0x2545_f491_4f6c_dd1d;
+        let mut next = move || {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            (state % 9) as i64 - 4
+        };
+        (0..count).map(|_| [next(), next(), next()]).collect()
+    }
+
+    fn all_pairs(points: &[[i64; 3]]) -> Vec<Pair> {
+        let mut pairs = Vec::new();
+        for a in 0..points.len() {
+            for b in a + 1..points.len() {
+                let squared_distance = squared_distance(points[a], points[b]);
+                pairs.push(Pair {
+                    squared_distance,
+                    a,
+                    b,
+                });
+            }
+        }
+        pairs.sort_unstable();
+        pairs
+    }
+
+    #[test]
+    fn squared_distances() {
+        assert_eq!(squared_distance([1, 2, 3], [4, 6, 3]), 25);
+        assert_eq!(squared_distance([5], [5]), 0);
+        assert_eq!(squared_distance::<0>([], []), 0);
+        let far = squared_distance([i64::MIN], [i64::MAX]);
+        assert_eq!(far, u128::from(u64::MAX).pow(2));
+        let far = squared_distance([0; 3], [i64::MAX, i64::MIN + 1, i64::MAX]);
+        assert_eq!(far, 3 * u128::from(u64::MAX >> 1).pow(2));
+    }
+
+    #[test]
+    #[should_panic(expected = "squared distance overflows")]
+    fn squared_distance_panics_past_u128() {
+        let _ = squared_distance([i64::MIN, i64::MIN], [i64::MAX, i64::MAX]);
+    }
+
+    #[test]
+    fn nearest_at_the_extremes() {
+        let points = [
+            [i64::MIN, 0, 0],
+            [i64::MAX, 0, 0],
+            [0, i64::MAX, i64::MIN + 1],
+            [0, 0, 0],
+        ];
+        let tree = KdTree::new(&points);
+        let max = u128::from(u64::MAX >> 1).pow(2);
+        assert_eq!(
+            tree.nearest([i64::MAX, 0, 0]).collect::<Vec<_>>(),
+            [
+                (0, 1),
+                (max, 3),
+                (3 * max, 2),
+                (u128::from(u64::MAX).pow(2), 0)
+            ]
+        );
+        assert_eq!(tree.nearest_pairs().collect::<Vec<_>>(), all_pairs(&points));
+        let order: Vec<(usize, usize)> =
+            tree.nearest_pairs().map(|pair| (pair.a, pair.b)).collect();
+        assert_eq!(order, [(1, 3), (0, 3), (2, 3), (1, 2), (0, 2), (0, 1)]);
+    }
+
+    #[test]
+    fn empty_tree() {
+        let tree = KdTree::<3>::new::<[i64; 3]>(&[]);
+        assert_eq!(tree.nearest([0, 0, 0]).next(), None);
+        assert_eq!(tree.nearest_pairs().next(), None);
+        assert!(minimum_spanning_tree::<3, [i64; 3]>(&[]).is_empty());
+    }
+
+    #[test]
+    fn single_point() {
+        let tree = KdTree::new(&[[3, 4]]);
+        assert_eq!(tree.nearest([0, 0]).collect::<Vec<_>>(), [(25, 0)]);
+        assert_eq!(tree.nearest_pairs().next(), None);
+        assert!(minimum_spanning_tree(&[[3, 4]]).is_empty());
+    }
+
+    #[test]
+    fn nearest_comes_out_in_order_of_distance_then_index() {
+        let points = scattered(60);
+        let tree = KdTree::new(&points);
+        for query in [[0, 0, 0], [4, -4, 4], [10, 0, -10], points[7]] {
+            let mut expected: Vec<(u128, usize)> = points
+                .iter()
+                .enumerate()
+                .map(|(i, &point)| (squared_distance(query, point), i))
+                .collect();
+            expected.sort_unstable();
+            assert_eq!(tree.nearest(query).collect::<Vec<_>>(), expected);
+        }
+    }
+
+    #[test]
+    fn nearest_pairs_match_sorting_every_pair() {
+        for count in [2, 3, 17, 60] {
+            let points = scattered(count);
+            let tree = KdTree::new(&points);
+            assert_eq!(tree.nearest_pairs().collect::<Vec<_>>(), all_pairs(&points));
+        }
+    }
+
+    #[test]
+    fn spanning_tree_joins_every_point_as_cheaply_as_kruskal_on_every_pair() {
+        for count in [2, 3, 17, 60] {
+            let points = scattered(count);
+            let edges = minimum_spanning_tree(&points);
+            assert_eq!(edges.len(), count - 1);
+            assert!(edges.is_sorted());
+
+            let mut components = DisjointSet::new(count);
+            let mut expected = Vec::new();
+            for pair in all_pairs(&points) {
+                if components.union(pair.a, pair.b) != Merge::Same {
+                    expected.push(pair);
+                }
+            }
+            assert_eq!(edges, expected);
+        }
+    }
+}
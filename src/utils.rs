@@ -1,10 +1,182 @@
+pub mod fetch;
+#[cfg(all(test, feature = "fetch"))]
+mod stub;
+pub mod submit;
+
+use crate::error::{Error, Result};
+use std::env;
 use std::fs::File;
-use std::io::Read;
-
-pub fn read_input(filename: &str) -> String {
-    let mut file = File::open(filename).expect("Failed to open input file");
-    let mut input = String::new();
-    file.read_to_string(&mut input)
-        .expect("Failed to read input file");
-    input
+use std::io::{self, Read};
+use std::str::FromStr;
+
+/// Where a day reads its puzzle input from.
+#[derive(Debug, Clone)]
+pub enum InputSource {
+    /// Standard input, selected with `-` on the command line.
+    Stdin,
+    /// A file on disk.
+    Path(String),
+    /// An in-memory string, e.g. an example from the puzzle text.
+    Inline(String),
+}
+
+impl InputSource {
+    /// Interpret a command line argument: `-` is stdin, anything else a path.
+    pub fn from_arg(arg: &str) -> Self {
+        match arg {
+            "-" => InputSource::Stdin,
+            path => InputSource::Path(path.to_string()),
+        }
+    }
+
+    /// Name used for this source in error messages.
+    pub fn name(&self) -> &str {
+        match self {
+            InputSource::Stdin => "<stdin>",
+            InputSource::Path(path) => path,
+            InputSource::Inline(_) => "<inline>",
+        }
+    }
+
+    /// Read the whole input and normalise it, see [`normalize`].
+    pub fn read(&self) -> Result<String> {
+        let io_error = |source| Error::Io {
+            path: self.name().to_string(),
+            source,
+        };
+        let mut input = String::new();
+        match self {
+            InputSource::Stdin => {
+                io::stdin().read_to_string(&mut input).map_err(io_error)?;
+            }
+            InputSource::Path(path) => {
+                let mut file = File::open(path).map_err(io_error)?;
+                file.read_to_string(&mut input).map_err(io_error)?;
+            }
+            InputSource::Inline(text) => input.push_str(text),
+        }
+        Ok(normalize(&input))
+    }
+}
+
+/// Return the input source passed as the first command line argument.
+pub fn input_source() -> Result<InputSource> {
+    input_source_from(env::args())
+}
+
+/// [`input_source`] for the given command line, program name first.
+fn input_source_from(mut args: impl Iterator<Item = String>) -> Result<InputSource> {
+    let program = args.next().unwrap_or_else(|| "day".into());
+    args.next()
+        .map(|arg| InputSource::from_arg(&arg))
+        .ok_or_else(|| Error::Usage(format!("{} <input file | ->", program)))
+}
+
+pub fn read_input(source: &InputSource) -> Result<String> {
+    source.read()
+}
+
+/// Normalise puzzle input so every source parses the same way: line endings
+/// become `\n`, blank lines after the last line of content are dropped, and
+/// the result ends with a single newline.
+///
+/// Blank lines before the content are kept, so that line numbers in parse
+/// errors match the original input. Whitespace inside and at the end of each
+/// line is kept too, since some puzzles (day06) lay their input out in
+/// fixed-width columns.
+pub fn normalize(input: &str) -> String {
+    let lines: Vec<&str> = input.lines().collect();
+    let end = lines
+        .iter()
+        .rposition(|line| !line.trim().is_empty())
+        .map_or(0, |last| last + 1);
+    let mut normalized = lines[..end].join("\n");
+    if !normalized.is_empty() {
+        normalized.push('\n');
+    }
+    normalized
+}
+
+/// Parse `value` found on the 1-based `line` of an input, describing the
+/// `expected` format if it can't be parsed.
+pub fn parse_value<T: FromStr>(value: &str, line: usize, expected: &str) -> Result<T> {
+    value
+        .trim()
+        .parse()
+        .map_err(|_| Error::parse(line, expected, value))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normalize_turns_crlf_into_lf() {
+        assert_eq!(normalize("a\r\nb\r\n"), "a\nb\n");
+    }
+
+    #[test]
+    fn normalize_trims_blank_lines_after_the_content() {
+        assert_eq!(normalize("a\nb\n\n \n"), "a\nb\n");
+        assert_eq!(normalize("\n\n"), "");
+        assert_eq!(normalize(""), "");
+    }
+
+    #[test]
+    fn normalize_keeps_blank_lines_before_the_content() {
+        assert_eq!(normalize("\n  \n\na\nb\n\n"), "\n  \n\na\nb\n");
+    }
+
+    #[test]
+    fn parse_errors_count_lines_from_the_top_of_the_original_input() {
+        let source = InputSource::Inline("\r\n\r\n7\r\nx\r\n".into());
+        let input = source.read().unwrap();
+        let error = input
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| !line.is_empty())
+            .map(|(idx, line)| parse_value::<u32>(line, idx + 1, "a number"))
+            .find_map(|value| value.err());
+        match error {
+            Some(Error::Parse { line, found, .. }) => assert_eq!((line, found.as_str()), (4, "x")),
+            other => panic!("expected a parse error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn normalize_keeps_inner_whitespace() {
+        let input = "123 328  51\n 45 64  387\n\n  6 98  215 \n";
+        assert_eq!(normalize(input), input);
+    }
+
+    #[test]
+    fn normalize_adds_a_final_newline() {
+        assert_eq!(normalize("a\nb"), "a\nb\n");
+    }
+
+    #[test]
+    fn inline_input_is_normalized_like_any_other() {
+        let source = InputSource::Inline("\r\n3-5\r\n\r\n1\r\n\r\n".into());
+        assert_eq!(source.name(), "<inline>");
+        assert_eq!(source.read().unwrap(), "\n3-5\n\n1\n");
+    }
+
+    #[test]
+    fn dash_reads_stdin() {
+        let args = ["day01", "-"].map(String::from).into_iter();
+        assert!(matches!(input_source_from(args), Ok(InputSource::Stdin)));
+    }
+
+    #[test]
+    fn other_arguments_are_paths() {
+        let args = ["day01", "inputs/day01"].map(String::from).into_iter();
+        let source = input_source_from(args).unwrap();
+        assert!(matches!(&source, InputSource::Path(path) if path == "inputs/day01"));
+    }
+
+    #[test]
+    fn missing_argument_is_a_usage_error() {
+        let args = ["day01"].map(String::from).into_iter();
+        assert!(matches!(input_source_from(args), Err(Error::Usage(_))));
+    }
 }
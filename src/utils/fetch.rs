@@ -0,0 +1,383 @@
+use crate::error::{Error, Result};
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+#[cfg(feature = "fetch")]
+use std::sync::Mutex;
+use std::time::Duration;
+#[cfg(feature = "fetch")]
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// The year whose inputs live directly in the cache directory as `dayNN`.
+pub const DEFAULT_YEAR: u16 = 2025;
+
+/// File in the cache directory where [`HttpClient`] records when it last
+/// sent a request, so that separate runs keep to the interval too.
+pub const LAST_REQUEST_FILE: &str = ".last-request";
+
+/// Settings for locating and downloading puzzle inputs, read from the
+/// environment so they can be pointed at a local stand-in server.
+#[derive(Debug, Clone)]
+pub struct FetchConfig {
+    /// Server to request inputs from (`AOC_BASE_URL`).
+    pub base_url: String,
+    /// Value of the `session` cookie (`AOC_SESSION`, or the contents of the
+    /// file named by `AOC_SESSION_FILE`, default `.aoc-session`).
+    pub session: Option<String>,
+    /// Directory holding cached inputs (`AOC_INPUT_DIR`).
+    pub cache_dir: PathBuf,
+    /// Minimum time between two requests to the server, across runs
+    /// sharing the cache directory (`AOC_MIN_INTERVAL`, in seconds).
+    pub min_interval: Duration,
+}
+
+impl Default for FetchConfig {
+    fn default() -> Self {
+        FetchConfig {
+            base_url: "https://adventofcode.com".into(),
+            session: None,
+            cache_dir: PathBuf::from("inputs"),
+            min_interval: Duration::from_secs(5),
+        }
+    }
+}
+
+impl FetchConfig {
+    pub fn from_env() -> Result<Self> {
+        let defaults = FetchConfig::default();
+        let session_file = env::var("AOC_SESSION_FILE").unwrap_or_else(|_| ".aoc-session".into());
+        let session = env::var("AOC_SESSION")
+            .ok()
+            .or_else(|| fs::read_to_string(session_file).ok())
+            .map(|token| token.trim().to_string())
+            .filter(|token| !token.is_empty());
+
+        Ok(FetchConfig {
+            base_url: env::var("AOC_BASE_URL")
+                .map(|url| url.trim_end_matches('/').to_string())
+                .unwrap_or(defaults.base_url),
+            session,
+            cache_dir: env::var("AOC_INPUT_DIR")
+                .map(PathBuf::from)
+                .unwrap_or(defaults.cache_dir),
+            min_interval: match env::var("AOC_MIN_INTERVAL") {
+                Ok(seconds) => parse_interval(&seconds)?,
+                Err(_) => defaults.min_interval,
+            },
+        })
+    }
+}
+
+/// A number of seconds like `5` or `0.5`, as given in `AOC_MIN_INTERVAL`.
+fn parse_interval(seconds: &str) -> Result<Duration> {
+    seconds
+        .trim()
+        .parse()
+        .ok()
+        .and_then(|seconds| Duration::try_from_secs_f64(seconds).ok())
+        .ok_or_else(|| {
+            Error::Config(format!(
+                "AOC_MIN_INTERVAL should be a number of seconds, not `{}`",
+                seconds
+            ))
+        })
+}
+
+/// Something that can produce the input for a puzzle, e.g. over HTTP.
+pub trait Fetcher {
+    fn fetch(&self, year: u16, day: u8) -> Result<String>;
+}
+
+/// Talks to the puzzle server using the session cookie, waiting at least
+/// `min_interval` between requests, including those of earlier runs that
+/// used the same cache directory. Inputs are fetched from
+/// `{base_url}/{year}/day/{day}/input`.
+#[cfg(feature = "fetch")]
+pub struct HttpClient {
+    base_url: String,
+    session: Option<String>,
+    min_interval: Duration,
+    last_request_file: PathBuf,
+    /// Held while waiting, so that threads sharing the client take turns.
+    turn: Mutex<()>,
+}
+
+#[cfg(feature = "fetch")]
+impl HttpClient {
+    pub fn new(config: &FetchConfig) -> Self {
+        HttpClient {
+            base_url: config.base_url.clone(),
+            session: config.session.clone(),
+            min_interval: config.min_interval,
+            last_request_file: config.cache_dir.join(LAST_REQUEST_FILE),
+            turn: Mutex::new(()),
+        }
+    }
+
+    /// Block until `min_interval` has passed since the previous request, as
+    /// recorded in [`LAST_REQUEST_FILE`] in nanoseconds since the Unix
+    /// epoch, then record this one. A missing or unreadable record doesn't
+    /// hold the request up, and failing to write one doesn't stop it.
+    fn wait_for_turn(&self) {
+        let _turn = self.turn.lock().unwrap();
+        let last = fs::read_to_string(&self.last_request_file)
+            .ok()
+            .and_then(|nanos| nanos.trim().parse().ok())
+            .map(|nanos| UNIX_EPOCH + Duration::from_nanos(nanos));
+        if let Some(elapsed) = last.and_then(|last| SystemTime::now().duration_since(last).ok())
+            && elapsed < self.min_interval
+        {
+            std::thread::sleep(self.min_interval - elapsed);
+        }
+
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_nanos();
+        if let Some(dir) = self.last_request_file.parent() {
+            let _ = fs::create_dir_all(dir);
+        }
+        let _ = fs::write(&self.last_request_file, now.to_string());
+    }
+
+    pub(crate) fn url(&self, path: &str) -> String {
+        format!("{}{}", self.base_url, path)
+    }
+
+    pub(crate) fn request(&self, method: &str, url: &str) -> Result<ureq::Request> {
+        let session = self.session.as_ref().ok_or_else(|| {
+            Error::Config("no session token, set AOC_SESSION or create .aoc-session".into())
+        })?;
+        self.wait_for_turn();
+        Ok(ureq::request(method, url)
+            .set("Cookie", &format!("session={}", session))
+            .set("User-Agent", "github.com/mkski/aoc2025"))
+    }
+}
+
+#[cfg(feature = "fetch")]
+pub(crate) fn request_error(url: &str, error: ureq::Error) -> Error {
+    match error {
+        ureq::Error::Status(status, _) => Error::Http {
+            url: url.to_string(),
+            status,
+        },
+        ureq::Error::Transport(transport) => Error::Offline {
+            url: url.to_string(),
+            reason: match std::error::Error::source(&transport) {
+                Some(source) => format!("{}: {}", transport.kind(), source),
+                None => transport.kind().to_string(),
+            },
+        },
+    }
+}
+
+#[cfg(feature = "fetch")]
+impl Fetcher for HttpClient {
+    fn fetch(&self, year: u16, day: u8) -> Result<String> {
+        let url = self.url(&format!("/{}/day/{}/input", year, day));
+        let response = self
+            .request("GET", &url)?
+            .call()
+            .map_err(|e| request_error(&url, e))?;
+        response.into_string().map_err(|source| Error::Io {
+            path: url.clone(),
+            source,
+        })
+    }
+}
+
+/// Resolves puzzle inputs to files in a cache directory, asking the fetcher
+/// for any that aren't cached yet.
+pub struct InputCache<F: Fetcher> {
+    dir: PathBuf,
+    fetcher: F,
+}
+
+impl<F: Fetcher> InputCache<F> {
+    pub fn new(dir: impl Into<PathBuf>, fetcher: F) -> Self {
+        InputCache {
+            dir: dir.into(),
+            fetcher,
+        }
+    }
+
+    /// Where the input for `(year, day)` is cached: `inputs/day01` for
+    /// [`DEFAULT_YEAR`], `inputs/2024/day01` for any other year.
+    pub fn path(&self, year: u16, day: u8) -> PathBuf {
+        let file = format!("day{:02}", day);
+        if year == DEFAULT_YEAR {
+            self.dir.join(file)
+        } else {
+            self.dir.join(year.to_string()).join(file)
+        }
+    }
+
+    /// Return the cached input file for `(year, day)`, fetching it first if
+    /// it isn't cached.
+    pub fn resolve(&self, year: u16, day: u8) -> Result<PathBuf> {
+        let path = self.path(year, day);
+        if path.exists() {
+            return Ok(path);
+        }
+
+        let input = self.fetcher.fetch(year, day)?;
+        let io_error = |source| Error::Io {
+            path: path.display().to_string(),
+            source,
+        };
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).map_err(io_error)?;
+        }
+        fs::write(&path, input).map_err(io_error)?;
+        Ok(path)
+    }
+}
+
+#[cfg(all(test, feature = "fetch"))]
+mod tests {
+    use super::*;
+    use crate::utils::stub::{StubServer, dead_url, scratch_dir};
+    use std::path::Path;
+    use std::time::Instant;
+
+    fn client(
+        base_url: &str,
+        session: Option<&str>,
+        cache_dir: &Path,
+        min_interval: Duration,
+    ) -> HttpClient {
+        HttpClient::new(&FetchConfig {
+            base_url: base_url.to_string(),
+            session: session.map(String::from),
+            cache_dir: cache_dir.to_path_buf(),
+            min_interval,
+        })
+    }
+
+    #[test]
+    fn cache_hit_skips_the_network() {
+        let dir = scratch_dir("cache-hit");
+        fs::write(dir.join("day01"), "cached\n").unwrap();
+        // Any request would be refused, failing the resolve.
+        let client = client(&dead_url(), Some("token"), &dir, Duration::ZERO);
+        let cache = InputCache::new(&dir, client);
+        let path = cache.resolve(DEFAULT_YEAR, 1).unwrap();
+        assert_eq!(path, dir.join("day01"));
+        assert_eq!(fs::read_to_string(path).unwrap(), "cached\n");
+    }
+
+    #[test]
+    fn cache_miss_fetches_and_writes_the_file() {
+        let dir = scratch_dir("cache-miss");
+        let server = StubServer::start(vec![(200, "1\n2\n"), (200, "old\n")]);
+        let cache = InputCache::new(
+            &dir,
+            client(&server.base_url, Some("token"), &dir, Duration::ZERO),
+        );
+
+        let path = cache.resolve(DEFAULT_YEAR, 3).unwrap();
+        assert_eq!(path, dir.join("day03"));
+        assert_eq!(fs::read_to_string(&path).unwrap(), "1\n2\n");
+        let path = cache.resolve(2024, 3).unwrap();
+        assert_eq!(path, dir.join("2024").join("day03"));
+        assert_eq!(fs::read_to_string(&path).unwrap(), "old\n");
+
+        let requests = server.requests();
+        assert_eq!(requests.len(), 2);
+        assert_eq!(requests[0].method, "GET");
+        assert_eq!(requests[0].path, "/2025/day/3/input");
+        assert_eq!(requests[0].header("Cookie"), Some("session=token"));
+        assert_eq!(requests[0].body, "");
+        assert_eq!(requests[1].path, "/2024/day/3/input");
+    }
+
+    #[test]
+    fn missing_session_is_a_config_error() {
+        let dir = scratch_dir("no-session");
+        let cache = InputCache::new(&dir, client(&dead_url(), None, &dir, Duration::ZERO));
+        assert!(matches!(
+            cache.resolve(DEFAULT_YEAR, 1),
+            Err(Error::Config(_))
+        ));
+        assert!(!dir.join("day01").exists());
+    }
+
+    #[test]
+    fn refused_connection_is_offline() {
+        let url = dead_url();
+        let dir = scratch_dir("offline");
+        let result = client(&url, Some("token"), &dir, Duration::ZERO).fetch(DEFAULT_YEAR, 1);
+        assert!(
+            matches!(result, Err(Error::Offline { url: failed, .. }) if failed.starts_with(&url))
+        );
+    }
+
+    #[test]
+    fn error_status_is_an_http_error() {
+        let server = StubServer::start(vec![(404, "not unlocked")]);
+        let dir = scratch_dir("http-error");
+        let result =
+            client(&server.base_url, Some("token"), &dir, Duration::ZERO).fetch(DEFAULT_YEAR, 12);
+        assert!(matches!(result, Err(Error::Http { status: 404, .. })));
+    }
+
+    #[test]
+    fn back_to_back_fetches_wait_for_the_interval() {
+        let interval = Duration::from_millis(200);
+        let server = StubServer::start(vec![(200, "a\n"), (200, "b\n")]);
+        let dir = scratch_dir("interval");
+        let client = client(&server.base_url, Some("token"), &dir, interval);
+        let start = Instant::now();
+        assert_eq!(client.fetch(DEFAULT_YEAR, 1).unwrap(), "a\n");
+        assert_eq!(client.fetch(DEFAULT_YEAR, 2).unwrap(), "b\n");
+        assert!(start.elapsed() >= interval);
+        assert_eq!(server.requests().len(), 2);
+    }
+
+    #[test]
+    fn separate_runs_sharing_a_cache_wait_for_the_interval() {
+        let interval = Duration::from_millis(200);
+        let server = StubServer::start(vec![(200, "a\n"), (200, "b\n")]);
+        let dir = scratch_dir("interval-across-runs");
+        let first = client(&server.base_url, Some("token"), &dir, interval);
+        assert_eq!(first.fetch(DEFAULT_YEAR, 1).unwrap(), "a\n");
+        assert!(dir.join(LAST_REQUEST_FILE).exists());
+
+        let start = Instant::now();
+        let second = client(&server.base_url, Some("token"), &dir, interval);
+        assert_eq!(second.fetch(DEFAULT_YEAR, 2).unwrap(), "b\n");
+        assert!(start.elapsed() >= interval / 2);
+    }
+
+    #[test]
+    fn unreadable_last_request_does_not_hold_up_a_request() {
+        let server = StubServer::start(vec![(200, "a\n")]);
+        let dir = scratch_dir("interval-garbled");
+        fs::write(dir.join(LAST_REQUEST_FILE), "yesterday").unwrap();
+        let start = Instant::now();
+        let client = client(
+            &server.base_url,
+            Some("token"),
+            &dir,
+            Duration::from_secs(60),
+        );
+        assert_eq!(client.fetch(DEFAULT_YEAR, 1).unwrap(), "a\n");
+        assert!(start.elapsed() < Duration::from_secs(30));
+        let recorded = fs::read_to_string(dir.join(LAST_REQUEST_FILE)).unwrap();
+        assert!(recorded.parse::<u128>().is_ok());
+    }
+
+    #[test]
+    fn interval_is_given_in_seconds() {
+        assert_eq!(parse_interval("5").unwrap(), Duration::from_secs(5));
+        assert_eq!(
+            parse_interval(" 0.25\n").unwrap(),
+            Duration::from_millis(250)
+        );
+        assert_eq!(parse_interval("0").unwrap(), Duration::ZERO);
+        for invalid in ["", "-1", "soon", "NaN"] {
+            assert!(matches!(parse_interval(invalid), Err(Error::Config(_))));
+        }
+    }
+}
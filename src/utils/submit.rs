@@ -0,0 +1,472 @@
+use crate::error::{Error, Result};
+#[cfg(feature = "fetch")]
+use crate::utils::fetch::{HttpClient, request_error};
+use std::fmt::{Display, Formatter};
+use std::fs::{self, OpenOptions};
+use std::io::{ErrorKind, Write};
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// How the server responded to a submitted answer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Verdict {
+    Correct,
+    TooHigh,
+    TooLow,
+    /// Wrong, without a hint about which direction.
+    Incorrect,
+    /// Submitted too soon after the previous answer, with the seconds left to
+    /// wait if the server said.
+    Wait(Option<u64>),
+    /// The part is already solved or not unlocked yet.
+    WrongLevel,
+    /// The response didn't contain any message we recognise.
+    Unknown,
+}
+
+impl Verdict {
+    /// Classify the body of the page returned after posting an answer.
+    pub fn from_response(body: &str) -> Self {
+        if body.contains("That's the right answer") {
+            Verdict::Correct
+        } else if body.contains("your answer is too high") {
+            Verdict::TooHigh
+        } else if body.contains("your answer is too low") {
+            Verdict::TooLow
+        } else if body.contains("That's not the right answer") {
+            Verdict::Incorrect
+        } else if body.contains("You gave an answer too recently") {
+            Verdict::Wait(parse_wait(body))
+        } else if body.contains("You don't seem to be solving the right level") {
+            Verdict::WrongLevel
+        } else {
+            Verdict::Unknown
+        }
+    }
+
+    /// Whether the answer is known to be wrong.
+    pub fn is_wrong(self) -> bool {
+        matches!(
+            self,
+            Verdict::TooHigh | Verdict::TooLow | Verdict::Incorrect
+        )
+    }
+}
+
+/// Read the seconds from "You have 1m 5s left to wait".
+fn parse_wait(body: &str) -> Option<u64> {
+    let (_, rest) = body.split_once("You have ")?;
+    let (wait, _) = rest.split_once(" left")?;
+    wait.split_whitespace().try_fold(0, |total, part| {
+        let (value, unit) = part.split_at(part.len().checked_sub(1)?);
+        let value: u64 = value.parse().ok()?;
+        match unit {
+            "h" => Some(total + value * 3600),
+            "m" => Some(total + value * 60),
+            "s" => Some(total + value),
+            _ => None,
+        }
+    })
+}
+
+impl Display for Verdict {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Verdict::Correct => write!(f, "correct"),
+            Verdict::TooHigh => write!(f, "too-high"),
+            Verdict::TooLow => write!(f, "too-low"),
+            Verdict::Incorrect => write!(f, "incorrect"),
+            Verdict::Wait(Some(seconds)) => write!(f, "wait-{}s", seconds),
+            Verdict::Wait(None) => write!(f, "wait"),
+            Verdict::WrongLevel => write!(f, "wrong-level"),
+            Verdict::Unknown => write!(f, "unknown"),
+        }
+    }
+}
+
+impl FromStr for Verdict {
+    type Err = ();
+
+    fn from_str(verdict: &str) -> std::result::Result<Self, ()> {
+        match verdict {
+            "correct" => Ok(Verdict::Correct),
+            "too-high" => Ok(Verdict::TooHigh),
+            "too-low" => Ok(Verdict::TooLow),
+            "incorrect" => Ok(Verdict::Incorrect),
+            "wait" => Ok(Verdict::Wait(None)),
+            "wrong-level" => Ok(Verdict::WrongLevel),
+            "unknown" => Ok(Verdict::Unknown),
+            other => other
+                .strip_prefix("wait-")
+                .and_then(|s| s.strip_suffix('s'))
+                .and_then(|s| s.parse().ok())
+                .map(|seconds| Verdict::Wait(Some(seconds)))
+                .ok_or(()),
+        }
+    }
+}
+
+/// One answer sent to the server and how it was judged.
+#[derive(Debug, Clone)]
+pub struct Submission {
+    pub year: u16,
+    pub day: u8,
+    pub part: u8,
+    pub answer: String,
+    pub verdict: Verdict,
+    /// Seconds since the Unix epoch.
+    pub timestamp: u64,
+}
+
+/// Every submission made from this checkout, kept as tab separated lines of
+/// `timestamp year day part answer verdict`.
+pub struct SubmissionHistory {
+    path: PathBuf,
+    submissions: Vec<Submission>,
+}
+
+impl SubmissionHistory {
+    /// Load the history from `path`, which doesn't need to exist yet.
+    pub fn load(path: impl Into<PathBuf>) -> Result<Self> {
+        const EXPECTED: &str = "`timestamp year day part answer verdict` separated by tabs";
+        let path = path.into();
+        let name = path.display().to_string();
+        let contents = match fs::read_to_string(&path) {
+            Ok(contents) => contents,
+            Err(e) if e.kind() == ErrorKind::NotFound => String::new(),
+            Err(source) => return Err(Error::Io { path: name, source }),
+        };
+
+        let mut submissions = Vec::new();
+        for (idx, line) in contents.lines().enumerate() {
+            let invalid = || Error::parse(idx + 1, EXPECTED, line).in_file(&name);
+            let fields: Vec<&str> = line.split('\t').collect();
+            let [timestamp, year, day, part, answer, verdict] = fields[..] else {
+                return Err(invalid());
+            };
+            submissions.push(Submission {
+                year: year.parse().map_err(|_| invalid())?,
+                day: day.parse().map_err(|_| invalid())?,
+                part: part.parse().map_err(|_| invalid())?,
+                answer: answer.to_string(),
+                verdict: verdict.parse().map_err(|_| invalid())?,
+                timestamp: timestamp.parse().map_err(|_| invalid())?,
+            });
+        }
+        Ok(SubmissionHistory { path, submissions })
+    }
+
+    pub fn submissions_for(
+        &self,
+        year: u16,
+        day: u8,
+        part: u8,
+    ) -> impl Iterator<Item = &Submission> {
+        self.submissions
+            .iter()
+            .filter(move |s| s.year == year && s.day == day && s.part == part)
+    }
+
+    /// Explain why `answer` is pointless to submit, if earlier verdicts
+    /// already rule it out.
+    pub fn check(&self, year: u16, day: u8, part: u8, answer: &str) -> Option<String> {
+        let previous: Vec<&Submission> = self.submissions_for(year, day, part).collect();
+        if let Some(solved) = previous.iter().find(|s| s.verdict == Verdict::Correct) {
+            return Some(format!(
+                "day {} part {} was already solved with `{}`",
+                day, part, solved.answer
+            ));
+        }
+        if let Some(rejected) = previous
+            .iter()
+            .find(|s| s.answer == answer && s.verdict.is_wrong())
+        {
+            return Some(format!("`{}` was already {}", answer, rejected.verdict));
+        }
+
+        let value: i128 = answer.parse().ok()?;
+        let bound = |verdict| {
+            previous
+                .iter()
+                .filter(move |s| s.verdict == verdict)
+                .filter_map(|s| s.answer.parse::<i128>().ok())
+        };
+        if let Some(high) = bound(Verdict::TooHigh).min()
+            && value >= high
+        {
+            return Some(format!("`{}` is not below `{}`, which was too high", value, high));
+        }
+        if let Some(low) = bound(Verdict::TooLow).max()
+            && value <= low
+        {
+            return Some(format!("`{}` is not above `{}`, which was too low", value, low));
+        }
+        None
+    }
+
+    /// Append a submission to the history file.
+    pub fn record(&mut self, submission: Submission) -> Result<()> {
+        let io_error = |source| Error::Io {
+            path: self.path.display().to_string(),
+            source,
+        };
+        if let Some(parent) = self.path.parent()
+            && !parent.as_os_str().is_empty()
+        {
+            fs::create_dir_all(parent).map_err(io_error)?;
+        }
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .map_err(io_error)?;
+        writeln!(
+            file,
+            "{}\t{}\t{}\t{}\t{}\t{}",
+            submission.timestamp,
+            submission.year,
+            submission.day,
+            submission.part,
+            submission.answer,
+            submission.verdict
+        )
+        .map_err(io_error)?;
+        self.submissions.push(submission);
+        Ok(())
+    }
+}
+
+/// Something that can send an answer for judging, e.g. over HTTP.
+pub trait Submitter {
+    fn submit(&self, year: u16, day: u8, part: u8, answer: &str) -> Result<Verdict>;
+}
+
+/// Posts answers to `{base_url}/{year}/day/{day}/answer`.
+#[cfg(feature = "fetch")]
+impl Submitter for HttpClient {
+    fn submit(&self, year: u16, day: u8, part: u8, answer: &str) -> Result<Verdict> {
+        let url = self.url(&format!("/{}/day/{}/answer", year, day));
+        let response = self
+            .request("POST", &url)?
+            .send_form(&[("level", &part.to_string()), ("answer", answer)])
+            .map_err(|e| request_error(&url, e))?;
+        let body = response.into_string().map_err(|source| Error::Io {
+            path: url.clone(),
+            source,
+        })?;
+        Ok(Verdict::from_response(&body))
+    }
+}
+
+/// Submit `answer` unless the history rules it out, recording the verdict.
+pub fn submit(
+    submitter: &impl Submitter,
+    history: &mut SubmissionHistory,
+    (year, day, part): (u16, u8, u8),
+    answer: &str,
+) -> Result<Verdict> {
+    if answer.is_empty() || answer.contains(char::is_whitespace) {
+        return Err(Error::Refused(format!("`{}` is not a valid answer", answer)));
+    }
+    if let Some(reason) = history.check(year, day, part, answer) {
+        return Err(Error::Refused(reason));
+    }
+    let verdict = submitter.submit(year, day, part, answer)?;
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_secs());
+    history.record(Submission {
+        year,
+        day,
+        part,
+        answer: answer.to_string(),
+        verdict,
+        timestamp,
+    })?;
+    Ok(verdict)
+}
+
+#[cfg(all(test, feature = "fetch"))]
+mod tests {
+    use super::*;
+    use crate::utils::fetch::FetchConfig;
+    use crate::utils::stub::{StubServer, dead_url, scratch_dir};
+    use std::path::Path;
+    use std::time::Duration;
+
+    const CORRECT: &str = "<p>That's the right answer!  You are one gold star closer.</p>";
+    const TOO_HIGH: &str = "<p>That's not the right answer; your answer is too high.</p>";
+    const TOO_LOW: &str = "<p>That's not the right answer; your answer is too low.</p>";
+    const WAIT: &str = "<p>You gave an answer too recently; you have to wait after \
+                        submitting an answer before trying again.  You have 1m 5s left to wait.</p>";
+
+    fn client(base_url: &str, cache_dir: &Path) -> HttpClient {
+        HttpClient::new(&FetchConfig {
+            base_url: base_url.to_string(),
+            session: Some("token".into()),
+            cache_dir: cache_dir.to_path_buf(),
+            min_interval: Duration::ZERO,
+        })
+    }
+
+    fn submission(part: u8, answer: &str, verdict: Verdict) -> Submission {
+        Submission {
+            year: 2025,
+            day: 1,
+            part,
+            answer: answer.to_string(),
+            verdict,
+            timestamp: 0,
+        }
+    }
+
+    #[test]
+    fn verdicts_are_read_from_the_response() {
+        assert_eq!(Verdict::from_response(CORRECT), Verdict::Correct);
+        assert_eq!(Verdict::from_response(TOO_HIGH), Verdict::TooHigh);
+        assert_eq!(Verdict::from_response(TOO_LOW), Verdict::TooLow);
+        assert_eq!(Verdict::from_response(WAIT), Verdict::Wait(Some(65)));
+        assert_eq!(Verdict::from_response("<p>???</p>"), Verdict::Unknown);
+    }
+
+    #[test]
+    fn verdicts_round_trip_through_their_names() {
+        for verdict in [
+            Verdict::Correct,
+            Verdict::TooHigh,
+            Verdict::TooLow,
+            Verdict::Incorrect,
+            Verdict::Wait(Some(65)),
+            Verdict::Wait(None),
+            Verdict::WrongLevel,
+            Verdict::Unknown,
+        ] {
+            assert_eq!(verdict.to_string().parse(), Ok(verdict));
+        }
+    }
+
+    #[test]
+    fn submit_posts_answers_and_records_each_verdict() {
+        let dir = scratch_dir("submit");
+        let path = dir.join("submissions.tsv");
+        let server = StubServer::start(vec![
+            (200, TOO_HIGH),
+            (200, TOO_LOW),
+            (200, WAIT),
+            (200, CORRECT),
+        ]);
+        let client = client(&server.base_url, &dir);
+        let mut history = SubmissionHistory::load(&path).unwrap();
+        let mut send = |answer| submit(&client, &mut history, (2025, 1, 2), answer).unwrap();
+        assert_eq!(send("50"), Verdict::TooHigh);
+        assert_eq!(send("20"), Verdict::TooLow);
+        assert_eq!(send("30"), Verdict::Wait(Some(65)));
+        // Waiting says nothing about the answer, so it can be sent again.
+        assert_eq!(send("30"), Verdict::Correct);
+
+        let requests = server.requests();
+        assert_eq!(requests.len(), 4);
+        assert_eq!(requests[0].method, "POST");
+        assert_eq!(requests[0].path, "/2025/day/1/answer");
+        assert_eq!(requests[0].header("Cookie"), Some("session=token"));
+        assert_eq!(requests[0].body, "level=2&answer=50");
+    }
+
+    #[test]
+    fn known_wrong_answers_are_refused() {
+        let dir = scratch_dir("submit-wrong");
+        let mut history = SubmissionHistory::load(dir.join("submissions.tsv")).unwrap();
+        history
+            .record(submission(1, "abc", Verdict::Incorrect))
+            .unwrap();
+        // Nothing is listening, so reaching the server would be an Offline
+        // error rather than a refusal.
+        let client = client(&dead_url(), &dir);
+        let result = submit(&client, &mut history, (2025, 1, 1), "abc");
+        assert!(matches!(result, Err(Error::Refused(_))));
+        let result = submit(&client, &mut history, (2025, 1, 1), "a b");
+        assert!(matches!(result, Err(Error::Refused(_))));
+        let result = submit(&client, &mut history, (2025, 1, 1), "");
+        assert!(matches!(result, Err(Error::Refused(_))));
+    }
+
+    #[test]
+    fn answers_outside_the_recorded_bounds_are_refused() {
+        let dir = scratch_dir("submit-bounds");
+        let mut history = SubmissionHistory::load(dir.join("submissions.tsv")).unwrap();
+        history
+            .record(submission(1, "100", Verdict::TooHigh))
+            .unwrap();
+        history
+            .record(submission(1, "10", Verdict::TooLow))
+            .unwrap();
+        let client = client(&dead_url(), &dir);
+        for answer in ["100", "150", "10", "5", "-3"] {
+            let result = submit(&client, &mut history, (2025, 1, 1), answer);
+            assert!(matches!(result, Err(Error::Refused(_))), "{}", answer);
+        }
+        assert_eq!(history.check(2025, 1, 1, "11"), None);
+        assert_eq!(history.check(2025, 1, 1, "99"), None);
+        // The bounds only apply to the part they were found for.
+        assert_eq!(history.check(2025, 1, 2, "150"), None);
+        assert_eq!(history.check(2024, 1, 1, "150"), None);
+    }
+
+    #[test]
+    fn solved_parts_are_refused() {
+        let dir = scratch_dir("submit-solved");
+        let mut history = SubmissionHistory::load(dir.join("submissions.tsv")).unwrap();
+        history
+            .record(submission(1, "42", Verdict::Correct))
+            .unwrap();
+        assert!(history.check(2025, 1, 1, "42").is_some());
+        assert!(history.check(2025, 1, 1, "43").is_some());
+        assert_eq!(history.check(2025, 1, 2, "42"), None);
+    }
+
+    #[test]
+    fn history_survives_a_reload() {
+        let dir = scratch_dir("submit-reload");
+        let path = dir.join("nested").join("submissions.tsv");
+        let mut history = SubmissionHistory::load(&path).unwrap();
+        assert_eq!(history.submissions_for(2025, 1, 1).count(), 0);
+        history
+            .record(submission(1, "100", Verdict::TooHigh))
+            .unwrap();
+        history
+            .record(submission(1, "7", Verdict::Wait(Some(30))))
+            .unwrap();
+        history
+            .record(submission(2, "x", Verdict::Incorrect))
+            .unwrap();
+
+        let reloaded = SubmissionHistory::load(&path).unwrap();
+        let verdicts = |part| -> Vec<(String, Verdict)> {
+            reloaded
+                .submissions_for(2025, 1, part)
+                .map(|s| (s.answer.clone(), s.verdict))
+                .collect()
+        };
+        assert_eq!(
+            verdicts(1),
+            [
+                ("100".to_string(), Verdict::TooHigh),
+                ("7".to_string(), Verdict::Wait(Some(30)))
+            ]
+        );
+        assert_eq!(verdicts(2), [("x".to_string(), Verdict::Incorrect)]);
+        assert!(reloaded.check(2025, 1, 1, "100").is_some());
+    }
+
+    #[test]
+    fn malformed_history_is_a_parse_error() {
+        let dir = scratch_dir("submit-malformed");
+        let path = dir.join("submissions.tsv");
+        fs::write(&path, "0\t2025\t1\t1\t42\tcorrect\n0\t2025\t1\n").unwrap();
+        assert!(matches!(
+            SubmissionHistory::load(&path),
+            Err(Error::Parse { line: 2, .. })
+        ));
+    }
+}